@@ -9,33 +9,94 @@ extern "C"
 
 
     GLFWwindow *_glfwCreateWindow(const char *title, int width, int height, GLFWframebuffersizefun callback)
+    {
+        WindowConfig config;
+        config.samples = 4;
+        config.context_version_major = 3;
+        config.context_version_minor = 3;
+        config.resizable = GLFW_TRUE;
+        config.decorated = GLFW_TRUE;
+        config.visible = GLFW_TRUE;
+        config.has_position = GLFW_FALSE;
+        config.pos_x = 0;
+        config.pos_y = 0;
+        config.monitor = -1;
+        config.swap_interval = -1;
+        config.headless = GLFW_FALSE;
+        return _glfwCreateWindowWithConfig(title, width, height, &config, callback);
+    }
+
+    GLFWwindow *_glfwCreateWindowWithConfig(const char *title, int width, int height, const WindowConfig *config, GLFWframebuffersizefun callback)
     {
         glfwSetErrorCallback(glfwErrorCallback);
+
+        // Headless windows use the null platform, which needs neither a display server nor a GPU.
+        // Init hints only take effect on the first glfwInit() call after a glfwTerminate().
+        glfwInitHint(GLFW_PLATFORM, config->headless ? GLFW_PLATFORM_NULL : GLFW_ANY_PLATFORM);
         
         if(!glfwInit()){
             return nullptr;
         }
 
+        // Start from a clean slate, hints persist across window creations
+        glfwDefaultWindowHints();
+
+        if (config->headless)
+        {
+            // Render through Mesa's OSMesa (llvmpipe) into an offscreen buffer
+            glfwWindowHint(GLFW_CONTEXT_CREATION_API, GLFW_OSMESA_CONTEXT_API);
+        }
+
         // Set MSAA samples for antialiasing
-        glfwWindowHint(GLFW_SAMPLES, 4);
+        glfwWindowHint(GLFW_SAMPLES, config->samples);
 
         // Tell GLFW what version of OpenGL we are using
-        // In this case we are using OpenGL 3.3 to be compatible with Mac
-        glfwWindowHint(GLFW_CONTEXT_VERSION_MAJOR, 3);
-        glfwWindowHint(GLFW_CONTEXT_VERSION_MINOR, 3);
+        // 3.3 is the default to be compatible with Mac
+        glfwWindowHint(GLFW_CONTEXT_VERSION_MAJOR, config->context_version_major);
+        glfwWindowHint(GLFW_CONTEXT_VERSION_MINOR, config->context_version_minor);
 
         // Tell GLFW we are using the CORE profile
         // So that means we only have the modern functions
         glfwWindowHint(GLFW_OPENGL_PROFILE, GLFW_OPENGL_CORE_PROFILE);
 
-        //glfwWindowHint(GLFW_DECORATED, GLFW_FALSE);        
+        glfwWindowHint(GLFW_RESIZABLE, config->resizable);
+        glfwWindowHint(GLFW_DECORATED, config->decorated);
+        glfwWindowHint(GLFW_VISIBLE, config->visible);
+
+        if (config->has_position)
+        {
+            glfwWindowHint(GLFW_POSITION_X, config->pos_x);
+            glfwWindowHint(GLFW_POSITION_Y, config->pos_y);
+        }
 
 #ifdef __APPLE__
         glfwWindowHint(GLFW_OPENGL_FORWARD_COMPAT, GL_TRUE);
 #endif
 
-        GLFWwindow *window = glfwCreateWindow(width, height, title, nullptr, nullptr);
-        //glfwCreateWindow(100, 100, "Title", glfwGetPrimaryMonitor(), NULL);
+        // Fullscreen on the requested monitor, falling back to windowed mode if it doesn't exist
+        GLFWmonitor *monitor = nullptr;
+        if (config->monitor >= 0)
+        {
+            int count;
+            GLFWmonitor **monitors = glfwGetMonitors(&count);
+            if (config->monitor < count)
+            {
+                monitor = monitors[config->monitor];
+                const GLFWvidmode *mode = glfwGetVideoMode(monitor);
+                glfwWindowHint(GLFW_REFRESH_RATE, mode->refreshRate);
+                if (width <= 0 || height <= 0)
+                {
+                    width = mode->width;
+                    height = mode->height;
+                }
+            }
+            else
+            {
+                std::cerr << "Monitor " << config->monitor << " not found, creating a windowed window" << std::endl;
+            }
+        }
+
+        GLFWwindow *window = glfwCreateWindow(width, height, title, monitor, nullptr);
         if (window == nullptr)
         {
             std::cerr << "Failed to create GLFW window" << std::endl;
@@ -45,6 +106,11 @@ extern "C"
         glfwMakeContextCurrent(window);
         glfwSetFramebufferSizeCallback(window, callback);
 
+        if (config->swap_interval >= 0)
+        {
+            glfwSwapInterval(config->swap_interval);
+        }
+
         // glad: load all OpenGL function pointers
         if (!gladLoadGLLoader((GLADloadproc)glfwGetProcAddress))
         {
@@ -55,7 +121,10 @@ extern "C"
         }
 
         // Enable MSAA (glEnable must come AFTER context is current and GLAD is loaded)
-        glEnable(GL_MULTISAMPLE);
+        if (config->samples > 0)
+        {
+            glEnable(GL_MULTISAMPLE);
+        }
         
         int fb_width, fb_height;
         glfwGetFramebufferSize(window, &fb_width, &fb_height);
@@ -102,6 +171,11 @@ extern "C"
         glfwTerminate();
     }
 
+    void _glfwSwapInterval(int interval)
+    {
+        glfwSwapInterval(interval);
+    }
+
     void _glfwSwapBuffers(GLFWwindow *window)
     {
         // Swap the back buffer with the front buffer
@@ -114,6 +188,22 @@ extern "C"
         glfwPollEvents();
     }
 
+    void _glfwWaitEvents()
+    {
+        glfwWaitEvents();
+    }
+
+    void _glfwWaitEventsTimeout(double timeout)
+    {
+        glfwWaitEventsTimeout(timeout);
+    }
+
+    void _glfwPostEmptyEvent()
+    {
+        // Safe to call from any thread
+        glfwPostEmptyEvent();
+    }
+
     double _glfwGetTime()
     {
         return glfwGetTime();
@@ -129,6 +219,36 @@ extern "C"
         glfwSetCursorPosCallback(window, callback);
     }
 
+    void _glfwSetMouseButtonCallback(GLFWwindow *window, GLFWmousebuttonfun callback)
+    {
+        glfwSetMouseButtonCallback(window, callback);
+    }
+
+    void _glfwSetKeyCallback(GLFWwindow *window, GLFWkeyfun callback)
+    {
+        glfwSetKeyCallback(window, callback);
+    }
+
+    void _glfwSetCharCallback(GLFWwindow *window, GLFWcharfun callback)
+    {
+        glfwSetCharCallback(window, callback);
+    }
+
+    void _glfwSetWindowFocusCallback(GLFWwindow *window, GLFWwindowfocusfun callback)
+    {
+        glfwSetWindowFocusCallback(window, callback);
+    }
+
+    void _glfwSetWindowCloseCallback(GLFWwindow *window, GLFWwindowclosefun callback)
+    {
+        glfwSetWindowCloseCallback(window, callback);
+    }
+
+    void _glfwSetDropCallback(GLFWwindow *window, GLFWdropfun callback)
+    {
+        glfwSetDropCallback(window, callback);
+    }
+
     void _glfwGetWindowSize(GLFWwindow *window, int *width, int *height)
     {
         glfwGetWindowSize(window, width, height);
@@ -137,8 +257,11 @@ extern "C"
     void _glClearColor(GLfloat x, GLfloat y, GLfloat z, GLfloat a)
     {
         glClearColor(x, y, z, a);
-        // Clean the back buffer and assign the new color to it
-        glClear(GL_COLOR_BUFFER_BIT);
+    }
+
+    void _glClear(GLbitfield mask)
+    {
+        glClear(mask);
     }
 
     void _glViewPort(GLint x, GLint y, GLsizei width, GLsizei height)
@@ -151,6 +274,16 @@ extern "C"
         glGetIntegerv(pname, data);
     }
 
+    void _glReadPixels(GLint x, GLint y, GLsizei width, GLsizei height, GLenum format, GLenum type, void *data)
+    {
+        glReadPixels(x, y, width, height, format, type, data);
+    }
+
+    void _glPixelStorei(GLenum pname, GLint param)
+    {
+        glPixelStorei(pname, param);
+    }
+
     GLuint _glGenBuffer()
     {
         unsigned int VBO;
@@ -205,11 +338,21 @@ extern "C"
         glVertexAttribPointer(index, size, type, normalized, stride, (void *)offset);
     }
 
+    void _glVertexAttribIPointer(GLuint index, GLint size, GLenum type, GLsizei stride, GLsizei offset)
+    {
+        glVertexAttribIPointer(index, size, type, stride, (void *)(uintptr_t)offset);
+    }
+
     void _glEnableVertexAttribArray(GLuint index)
     {
         glEnableVertexAttribArray(index);
     }
 
+    void _glDisableVertexAttribArray(GLuint index)
+    {
+        glDisableVertexAttribArray(index);
+    }
+
     GLint _glGenTexture()
     {
         unsigned int texture;
@@ -235,15 +378,82 @@ extern "C"
     void _glTexImage2D(GLenum target, GLint level, GLint internalformat, GLsizei width, GLsizei height, GLint border, GLenum format, GLenum type, const void *data)
     {
         glTexImage2D(target, level, internalformat, width, height, border, format, type, data);
+#ifndef NDEBUG
         GLenum error = glGetError();
-        if (error == GL_INVALID_OPERATION)
-        {
-            printf("OpenGL error: %d\n", error);
-        }
-        else
+        if (error != GL_NO_ERROR)
         {
-            std::cout << "glTextImage2D called successfully" << std::endl;
+            printf("glTexImage2D error: %d\n", error);
         }
+#endif
+    }
+
+    void _glTexSubImage2D(GLenum target, GLint level, GLint xoffset, GLint yoffset, GLsizei width, GLsizei height, GLenum format, GLenum type, const void *data)
+    {
+        glTexSubImage2D(target, level, xoffset, yoffset, width, height, format, type, data);
+    }
+
+    void _glDeleteTexture(GLuint texture)
+    {
+        glDeleteTextures(1, &texture);
+    }
+
+    GLuint _glGenFramebuffer()
+    {
+        GLuint framebuffer;
+        glGenFramebuffers(1, &framebuffer);
+        return framebuffer;
+    }
+
+    void _glDeleteFramebuffer(GLuint framebuffer)
+    {
+        glDeleteFramebuffers(1, &framebuffer);
+    }
+
+    void _glBindFramebuffer(GLenum target, GLuint framebuffer)
+    {
+        glBindFramebuffer(target, framebuffer);
+    }
+
+    void _glFramebufferTexture2D(GLenum target, GLenum attachment, GLenum textarget, GLuint texture, GLint level)
+    {
+        glFramebufferTexture2D(target, attachment, textarget, texture, level);
+    }
+
+    GLenum _glCheckFramebufferStatus(GLenum target)
+    {
+        return glCheckFramebufferStatus(target);
+    }
+
+    GLuint _glGenRenderbuffer()
+    {
+        GLuint renderbuffer;
+        glGenRenderbuffers(1, &renderbuffer);
+        return renderbuffer;
+    }
+
+    void _glDeleteRenderbuffer(GLuint renderbuffer)
+    {
+        glDeleteRenderbuffers(1, &renderbuffer);
+    }
+
+    void _glBindRenderbuffer(GLenum target, GLuint renderbuffer)
+    {
+        glBindRenderbuffer(target, renderbuffer);
+    }
+
+    void _glRenderbufferStorageMultisample(GLenum target, GLsizei samples, GLenum internalformat, GLsizei width, GLsizei height)
+    {
+        glRenderbufferStorageMultisample(target, samples, internalformat, width, height);
+    }
+
+    void _glFramebufferRenderbuffer(GLenum target, GLenum attachment, GLenum renderbuffertarget, GLuint renderbuffer)
+    {
+        glFramebufferRenderbuffer(target, attachment, renderbuffertarget, renderbuffer);
+    }
+
+    void _glBlitFramebuffer(GLint srcX0, GLint srcY0, GLint srcX1, GLint srcY1, GLint dstX0, GLint dstY0, GLint dstX1, GLint dstY1, GLbitfield mask, GLenum filter)
+    {
+        glBlitFramebuffer(srcX0, srcY0, srcX1, srcY1, dstX0, dstY0, dstX1, dstY1, mask, filter);
     }
 
     void _glGenerateMipmap(GLenum target)
@@ -264,21 +474,6 @@ extern "C"
     void _glCompileShader(GLuint shader)
     {
         glCompileShader(shader);
-#ifndef NDEBUG
-        int success;
-        char infoLog[512];
-        glGetShaderiv(shader, GL_COMPILE_STATUS, &success);
-        if (!success)
-        {
-            glGetShaderInfoLog(shader, 512, NULL, infoLog);
-            std::cout << "ERROR::SHADER::VERTEX::COMPILATION_FAILED\n"
-                      << infoLog << std::endl;
-        }
-        else
-        {
-            std::cout << "shader " << shader << " compiled successfully" << std::endl;
-        }
-#endif
     }
 
     void _glDeleteShader(GLuint shader)
@@ -291,6 +486,11 @@ extern "C"
         glGetShaderiv(shader, pname, params);
     }
 
+    void _glGetShaderInfoLog(GLuint shader, GLsizei maxLength, GLsizei *length, GLchar *infoLog)
+    {
+        glGetShaderInfoLog(shader, maxLength, length, infoLog);
+    }
+
     GLuint _glCreateProgram()
     {
         return glCreateProgram();
@@ -306,6 +506,16 @@ extern "C"
         glLinkProgram(program);
     }
 
+    void _glGetProgramiv(GLuint program, GLenum pname, GLint *params)
+    {
+        glGetProgramiv(program, pname, params);
+    }
+
+    void _glGetProgramInfoLog(GLuint program, GLsizei maxLength, GLsizei *length, GLchar *infoLog)
+    {
+        glGetProgramInfoLog(program, maxLength, length, infoLog);
+    }
+
     void _glDeleteProgram(GLuint program)
     {
         glDeleteProgram(program);
@@ -333,7 +543,12 @@ extern "C"
 
     void _glDrawElements(GLenum mode, GLsizei count, GLenum type, GLuint offset)
     {
-        glDrawElements(mode, count, type, (void *)(offset));
+        glDrawElements(mode, count, type, (void *)(uintptr_t)(offset));
+    }
+
+    void _glDrawElementsInstanced(GLenum mode, GLsizei count, GLenum type, GLuint offset, GLsizei instancecount)
+    {
+        glDrawElementsInstanced(mode, count, type, (void *)(uintptr_t)(offset), instancecount);
     }
 
     GLint _glGetUniformLocation(GLuint program, GLchar *name)
@@ -341,6 +556,26 @@ extern "C"
         return glGetUniformLocation(program, name);
     }
 
+    void _glGetActiveAttrib(GLuint program, GLuint index, GLsizei bufSize, GLsizei *length, GLint *size, GLenum *type, GLchar *name)
+    {
+        glGetActiveAttrib(program, index, bufSize, length, size, type, name);
+    }
+
+    GLint _glGetAttribLocation(GLuint program, GLchar *name)
+    {
+        return glGetAttribLocation(program, name);
+    }
+
+    void _glGetActiveUniform(GLuint program, GLuint index, GLsizei bufSize, GLsizei *length, GLint *size, GLenum *type, GLchar *name)
+    {
+        glGetActiveUniform(program, index, bufSize, length, size, type, name);
+    }
+
+    void _glUniform1i(GLint location, GLint v0)
+    {
+        glUniform1i(location, v0);
+    }
+
     void _glUniform1f(GLint location, GLfloat v0)
     {
         glUniform1f(location, v0);
@@ -361,6 +596,11 @@ extern "C"
         glUniform4f(location, v0, v1, v2, v3);
     }
 
+    void _glUniformMatrix3fv(GLint location, GLsizei count, GLboolean transpose, const GLfloat *value)
+    {
+        glUniformMatrix3fv(location, count, transpose, value);
+    }
+
     void _glUniformMatrix4fv(GLint location, GLsizei count, GLboolean transpose, const GLfloat *value)
     {
         glUniformMatrix4fv(location, count, transpose, value);
@@ -376,11 +616,26 @@ extern "C"
         glEnable(cap);
     }
 
+    void _glDisable(GLenum cap)
+    {
+        glDisable(cap);
+    }
+
     void _glBlendFunc(GLenum sfactor, GLenum dfactor)
     {
         glBlendFunc(sfactor, dfactor);
     }
 
+    void _glBlendFuncSeparate(GLenum srcRGB, GLenum dstRGB, GLenum srcAlpha, GLenum dstAlpha)
+    {
+        glBlendFuncSeparate(srcRGB, dstRGB, srcAlpha, dstAlpha);
+    }
+
+    void _glBlendEquation(GLenum mode)
+    {
+        glBlendEquation(mode);
+    }
+
     int _glfwGetPlatform()
     {
         return glfwGetPlatform();
@@ -1,11 +1,30 @@
+#include <cstdint>
 #include <iostream>
 #include <glad/glad.h>
 #include <GLFW/glfw3.h>
 
 extern "C"
 {
+    // Window creation options, mirrored by `WindowConfig` in src/core/engine/glfw.rs
+    typedef struct
+    {
+        int samples;
+        int context_version_major;
+        int context_version_minor;
+        int resizable;
+        int decorated;
+        int visible;
+        int has_position;
+        int pos_x;
+        int pos_y;
+        int monitor;       // -1 for windowed mode, otherwise an index into glfwGetMonitors()
+        int swap_interval; // -1 leaves the driver default
+        int headless;      // null platform with an OSMesa context, no display required
+    } WindowConfig;
+
     // GFLW
     GLFWwindow *_glfwCreateWindow(const char *title, int width, int height, GLFWframebuffersizefun callback);
+    GLFWwindow *_glfwCreateWindowWithConfig(const char *title, int width, int height, const WindowConfig *config, GLFWframebuffersizefun callback);
     void _glfwSetWindowUserPointer(GLFWwindow *window, void *pointer);
     void *_glfwGetWindowUserPointer(GLFWwindow *window);
 
@@ -17,30 +36,46 @@ extern "C"
     void _glfwTerminate();
 
     void _glfwSwapBuffers(GLFWwindow *window);
+    void _glfwSwapInterval(int interval);
     void _glfwPollEvents();
+    void _glfwWaitEvents();
+    void _glfwWaitEventsTimeout(double timeout);
+    void _glfwPostEmptyEvent();
 
     double _glfwGetTime();
 
     void _glfwSetWindowSizeCallback(GLFWwindow *window, GLFWwindowsizefun callback);
     void _glfwSetScrollCallback(GLFWwindow *window, GLFWscrollfun callback);
     void _glfwSetCursorPosCallback(GLFWwindow *window, GLFWcursorposfun callback);
+    void _glfwSetMouseButtonCallback(GLFWwindow *window, GLFWmousebuttonfun callback);
+    void _glfwSetKeyCallback(GLFWwindow *window, GLFWkeyfun callback);
+    void _glfwSetCharCallback(GLFWwindow *window, GLFWcharfun callback);
+    void _glfwSetWindowFocusCallback(GLFWwindow *window, GLFWwindowfocusfun callback);
+    void _glfwSetWindowCloseCallback(GLFWwindow *window, GLFWwindowclosefun callback);
+    void _glfwSetDropCallback(GLFWwindow *window, GLFWdropfun callback);
 
     void _glfwGetWindowSize(GLFWwindow *window, int *width, int *height);
     void _glfwWindowHint(int hint, int value);
 
     // GL
     void _glClearColor(GLfloat x, GLfloat y, GLfloat z, GLfloat a);
+    void _glClear(GLbitfield mask);
     void _glViewPort(GLint x, GLint y, GLsizei width, GLsizei height);
     void _glGetIntegerv(GLenum pname, GLint *data);
+    void _glReadPixels(GLint x, GLint y, GLsizei width, GLsizei height, GLenum format, GLenum type, void *data);
+    void _glPixelStorei(GLenum pname, GLint param);
 
     GLuint _glCreateShader(GLenum shaderType);
     void _glShaderSource(GLuint shader, GLchar *source);
     void _glCompileShader(GLuint shader);
     void _glDeleteShader(GLuint shader);
     void _glGetShaderiv(GLuint shader, GLenum pname, GLint *params);
+    void _glGetShaderInfoLog(GLuint shader, GLsizei maxLength, GLsizei *length, GLchar *infoLog);
     GLuint _glCreateProgram();
     void _glAttachShader(GLuint program, GLuint shader);
     void _glLinkProgram(GLuint program);
+    void _glGetProgramiv(GLuint program, GLenum pname, GLint *params);
+    void _glGetProgramInfoLog(GLuint program, GLsizei maxLength, GLsizei *length, GLchar *infoLog);
     void _glDeleteProgram(GLuint program);
     void _glUseProgram(GLuint program);
     GLuint _glGenBuffer();
@@ -56,25 +91,50 @@ extern "C"
     void _glTexImage2D(GLenum target, GLint level, GLint internalformat, GLsizei width, GLsizei height, GLint border, GLenum format, GLenum type, const void *data);
     void _glTexParameteri(GLenum target, GLenum pname, GLint param);
     void _glGenerateMipmap(GLenum target);
+    void _glTexSubImage2D(GLenum target, GLint level, GLint xoffset, GLint yoffset, GLsizei width, GLsizei height, GLenum format, GLenum type, const void *data);
+    void _glDeleteTexture(GLuint texture);
+
+    GLuint _glGenFramebuffer();
+    void _glDeleteFramebuffer(GLuint framebuffer);
+    void _glBindFramebuffer(GLenum target, GLuint framebuffer);
+    void _glFramebufferTexture2D(GLenum target, GLenum attachment, GLenum textarget, GLuint texture, GLint level);
+    GLenum _glCheckFramebufferStatus(GLenum target);
+    GLuint _glGenRenderbuffer();
+    void _glDeleteRenderbuffer(GLuint renderbuffer);
+    void _glBindRenderbuffer(GLenum target, GLuint renderbuffer);
+    void _glRenderbufferStorageMultisample(GLenum target, GLsizei samples, GLenum internalformat, GLsizei width, GLsizei height);
+    void _glFramebufferRenderbuffer(GLenum target, GLenum attachment, GLenum renderbuffertarget, GLuint renderbuffer);
+    void _glBlitFramebuffer(GLint srcX0, GLint srcY0, GLint srcX1, GLint srcY1, GLint dstX0, GLint dstY0, GLint dstX1, GLint dstY1, GLbitfield mask, GLenum filter);
 
     GLuint _glGenVertexArray();
     void _glDeleteVertexArray(GLuint vao);
     void _glBindVertexArray(GLuint VAO);
     void _glVertexAttribPointer(GLuint index, GLint size, GLenum type, GLboolean normalized, GLsizei stride, GLsizei offset);
+    void _glVertexAttribIPointer(GLuint index, GLint size, GLenum type, GLsizei stride, GLsizei offset);
     void _glEnableVertexAttribArray(GLuint index);
+    void _glDisableVertexAttribArray(GLuint index);
 
     void _glDrawArrays(GLenum mode, GLint first, GLsizei count);
     void _glDrawArraysInstanced(GLenum mode, GLint first, GLsizei count, GLsizei instancecount);
     void _glVertexAttribDivisor(GLuint index, GLuint divisor);
 
     void _glDrawElements(GLenum mode, GLsizei count, GLenum type, GLuint offset);
+    void _glDrawElementsInstanced(GLenum mode, GLsizei count, GLenum type, GLuint offset, GLsizei instancecount);
     GLint _glGetUniformLocation(GLuint program, GLchar *name);
+    void _glGetActiveAttrib(GLuint program, GLuint index, GLsizei bufSize, GLsizei *length, GLint *size, GLenum *type, GLchar *name);
+    GLint _glGetAttribLocation(GLuint program, GLchar *name);
+    void _glGetActiveUniform(GLuint program, GLuint index, GLsizei bufSize, GLsizei *length, GLint *size, GLenum *type, GLchar *name);
+    void _glUniform1i(GLint location, GLint v0);
     void _glUniform1f(GLint location, GLfloat v0);
     void _glUniform2f(GLint location, GLfloat v0, GLfloat v1);
     void _glUniform3f(GLint location, GLfloat v0, GLfloat v1, GLfloat v2);
     void _glUniform4f(GLint location, GLfloat v0, GLfloat v1, GLfloat v2, GLfloat v3);
+    void _glUniformMatrix3fv(GLint location, GLsizei count, GLboolean transpose, const GLfloat *value);
     void _glUniformMatrix4fv(GLint location, GLsizei count, GLboolean transpose, const GLfloat *value);
     void _glPointSize(GLfloat size);
     void _glEnable(GLenum cap);
+    void _glDisable(GLenum cap);
     void _glBlendFunc(GLenum sfactor, GLenum dfactor);
+    void _glBlendFuncSeparate(GLenum srcRGB, GLenum dstRGB, GLenum srcAlpha, GLenum dstAlpha);
+    void _glBlendEquation(GLenum mode);
 };
\ No newline at end of file
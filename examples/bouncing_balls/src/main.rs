@@ -41,6 +41,7 @@ fn main() {
                     )),
                     stroke_color: None,
                     stroke_width: None,
+                    ..ShapeStyle::default()
                 },
             )
         })
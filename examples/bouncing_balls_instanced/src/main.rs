@@ -33,6 +33,7 @@ fn main() {
             fill: Some(Color::from_rgb(0.254902, 0.411765, 0.882353)),
             stroke_color: None,
             stroke_width: None,
+            ..ShapeStyle::default()
         },
     );
     dots.create_multiple_instances(balls.len());
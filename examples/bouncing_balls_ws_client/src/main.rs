@@ -1,7 +1,7 @@
-use sky_renderer::core::{App, Color, Renderable, Renderer, Window};
+use sky_renderer::core::{App, Color, DataSender, RedrawPolicy, Renderable, Renderer, Window};
 use sky_renderer::graphics2d::shapes::{Circle, ShapeKind, ShapeRenderable, ShapeStyle};
 
-use std::sync::{Arc, RwLock};
+use std::cell::RefCell;
 use tokio::runtime::Runtime;
 use tokio_tungstenite::connect_async;
 use futures_util::StreamExt;
@@ -22,15 +22,8 @@ const SCREEN_HEIGHT: i32 = 600;
 const BALL_RADIUS: f32 = 10.0;
 
 fn main() {
-    let positions: Arc<RwLock<Vec<BallSnapshot>>> = Arc::new(RwLock::new(vec![]));
-
-    {
-        let positions_clone = Arc::clone(&positions);
-        std::thread::spawn(move || {
-            let rt = Runtime::new().unwrap();
-            rt.block_on(run_ws_receiver(positions_clone));
-        });
-    }
+    let positions: RefCell<Vec<BallSnapshot>> = RefCell::new(vec![]);
+    let positions = &positions;
 
     let window = Window::new("WS Client Viewer", SCREEN_WIDTH, SCREEN_HEIGHT);
 
@@ -38,12 +31,26 @@ fn main() {
     renderer.set_point_size(6.0);
 
     let mut app = App::new(window);
+    // only redraw when a new snapshot arrives
+    app.set_redraw_policy(RedrawPolicy::OnDemand);
+
+    let (sender, feed) = app.data_feed::<Vec<BallSnapshot>>();
+    std::thread::spawn(move || {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(run_ws_receiver(sender));
+    });
+
+    app.attach_receiver(feed, move |snapshots| {
+        // only the most recent snapshot matters
+        if let Some(latest) = snapshots.into_iter().last() {
+            *positions.borrow_mut() = latest;
+        }
+    });
 
     let mut shapes: Vec<ShapeRenderable> = Vec::new();
 
-    let positions_render = Arc::clone(&positions);
     app.on_render(move || {
-        let pos_data = positions_render.read().unwrap();
+        let pos_data = positions.borrow();
 
         if pos_data.len() > shapes.len() {
             for snap in &pos_data[shapes.len()..] {
@@ -55,6 +62,7 @@ fn main() {
                         fill: Some(Color::from_rgb(snap.r, snap.g, snap.b)),
                         stroke_color: None,
                         stroke_width: None,
+                        ..ShapeStyle::default()
                     },
                 ));
             }
@@ -69,7 +77,7 @@ fn main() {
     app.run();
 }
 
-async fn run_ws_receiver(shared: Arc<RwLock<Vec<BallSnapshot>>>) {
+async fn run_ws_receiver(sender: DataSender<Vec<BallSnapshot>>) {
     let url = url::Url::parse("ws://127.0.0.1:9001").unwrap();
     println!("🔌 Connecting to {}", url);
 
@@ -81,8 +89,10 @@ async fn run_ws_receiver(shared: Arc<RwLock<Vec<BallSnapshot>>>) {
     while let Some(Ok(msg)) = reader.next().await {
         if msg.is_text() {
             if let Ok(parsed) = serde_json::from_str::<Vec<BallSnapshot>>(msg.to_text().unwrap()) {
-                let mut lock = shared.write().unwrap();
-                *lock = parsed;
+                if sender.send(parsed).is_err() {
+                    // the viewer window was closed
+                    break;
+                }
             }
         }
     }
@@ -0,0 +1,117 @@
+extern crate sky_renderer;
+
+use sky_renderer::core::engine::opengl::GL_TRIANGLE_STRIP;
+use sky_renderer::core::{
+    App, Geometry, Mesh, Renderer, Shader, Texture, TextureFilter, TextureFormat, TextureOptions,
+    TextureWrap, Window,
+};
+use sky_renderer::impl_vertex;
+use std::rc::Rc;
+
+const FIELD_SIZE: u32 = 128;
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct QuadVertex {
+    position: [f32; 2],
+    tex_coord: [f32; 2],
+}
+
+impl_vertex!(QuadVertex {
+    position => 0,
+    tex_coord => 1,
+});
+
+/// A scalar field in [0, 1], standing in for a streamed weather raster.
+fn field(t: f32) -> Vec<f32> {
+    let n = FIELD_SIZE as usize;
+    let mut values = Vec::with_capacity(n * n);
+    for j in 0..n {
+        for i in 0..n {
+            let (x, y) = (i as f32 / n as f32, j as f32 / n as f32);
+            let v = (x * 9.0 + t).sin() * (y * 7.0 - t * 0.7).cos();
+            values.push(0.5 + 0.5 * v);
+        }
+    }
+    values
+}
+
+/// Blue to white to red.
+fn colormap() -> Vec<u8> {
+    (0..256)
+        .flat_map(|i| {
+            let t = i as f32 / 255.0;
+            let (r, g, b) = if t < 0.5 {
+                (t * 2.0, t * 2.0, 1.0)
+            } else {
+                (1.0, 2.0 - t * 2.0, 2.0 - t * 2.0)
+            };
+            [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, 255]
+        })
+        .collect()
+}
+
+fn main() {
+    let window = Window::new("Colormap", 800, 800);
+
+    let quad = [
+        QuadVertex {
+            position: [-1.0, -1.0],
+            tex_coord: [0.0, 0.0],
+        },
+        QuadVertex {
+            position: [1.0, -1.0],
+            tex_coord: [1.0, 0.0],
+        },
+        QuadVertex {
+            position: [-1.0, 1.0],
+            tex_coord: [0.0, 1.0],
+        },
+        QuadVertex {
+            position: [1.0, 1.0],
+            tex_coord: [1.0, 1.0],
+        },
+    ];
+    let geometry = Geometry::from_vertices(GL_TRIANGLE_STRIP, &quad);
+
+    let shader = Shader::compile(
+        include_str!("shaders/colormap.vert"),
+        include_str!("shaders/colormap.frag"),
+        None,
+    )
+    .unwrap_or_else(|e| panic!("Failed to compile shader: {e}"));
+
+    let data = Rc::new(Texture::from_data(
+        FIELD_SIZE,
+        FIELD_SIZE,
+        &field(0.0),
+        TextureOptions::default()
+            .format(TextureFormat::R32F)
+            .wrap(TextureWrap::ClampToEdge)
+            .mipmaps(false),
+    ));
+    let lut = Rc::new(Texture::from_data(
+        256,
+        1,
+        &colormap(),
+        TextureOptions::default()
+            .wrap(TextureWrap::ClampToEdge)
+            .filter(TextureFilter::Linear)
+            .mipmaps(false),
+    ));
+
+    let mut mesh = Mesh::new(Rc::new(shader), geometry);
+    mesh.set_texture("u_data", Rc::clone(&data));
+    mesh.set_texture("u_colormap", lut);
+
+    let renderer = Renderer::new(window.handle());
+    let mut app = App::new(window);
+
+    app.on_render(move || {
+        // stream a new field into the existing texture
+        let t = renderer.get_time() as f32;
+        data.update_region(0, 0, FIELD_SIZE, FIELD_SIZE, &field(t));
+        renderer.draw_mesh(&mesh);
+    });
+    app.run();
+}
@@ -0,0 +1,46 @@
+extern crate sky_renderer;
+
+use sky_renderer::core::{Color, Renderable, Renderer, Window};
+use sky_renderer::graphics2d::shapes::{Circle, Rectangle, ShapeKind, ShapeRenderable, ShapeStyle};
+
+// Renders a few shapes without a display and writes the result to headless.png.
+// Requires Mesa's libOSMesa at runtime.
+fn main() {
+    let window = Window::headless(400, 300);
+    let renderer = Renderer::new(window.handle());
+
+    let mut shapes = vec![
+        ShapeRenderable::from_shape(
+            50.0,
+            50.0,
+            ShapeKind::Rectangle(Rectangle::new(200.0, 80.0)),
+            ShapeStyle {
+                fill: Some(Color::from_rgb(0.2, 0.5, 0.9)),
+                stroke_color: None,
+                stroke_width: None,
+                ..ShapeStyle::default()
+            },
+        ),
+        ShapeRenderable::from_shape(
+            300.0,
+            200.0,
+            ShapeKind::Circle(Circle::new(50.0)),
+            ShapeStyle {
+                fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+                stroke_color: None,
+                stroke_width: None,
+                ..ShapeStyle::default()
+            },
+        ),
+    ];
+
+    window.clear_color(0.07, 0.13, 0.17, 1.0);
+    for shape in &mut shapes {
+        shape.render(&renderer);
+    }
+
+    renderer
+        .save_screenshot("headless.png")
+        .expect("Failed to save screenshot");
+    println!("Saved headless.png");
+}
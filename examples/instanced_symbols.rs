@@ -0,0 +1,58 @@
+extern crate sky_renderer;
+
+use std::f32::consts::TAU;
+
+use sky_renderer::core::{App, Color, Renderable, Renderer, Vec2, Window};
+use sky_renderer::graphics2d::shapes::{
+    ShapeInstance, ShapeKind, ShapeRenderable, ShapeStyle, Triangle,
+};
+
+const WIDTH: i32 = 1600;
+const HEIGHT: i32 = 1000;
+const COLS: usize = 80;
+const ROWS: usize = 50;
+const SPACING: f32 = 19.0;
+const ORIGIN: f32 = 30.0;
+
+fn main() {
+    let window = Window::new("Instanced Symbols", WIDTH, HEIGHT);
+    let renderer = Renderer::new(window.handle());
+
+    // One arrow-head symbol pointing right, centered on its origin
+    let mut symbols = ShapeRenderable::from_shape(
+        0.0,
+        0.0,
+        ShapeKind::Triangle(Triangle::new([(8.0, 0.0), (-5.0, -5.0), (-5.0, 5.0)])),
+        ShapeStyle::default(),
+    );
+    let count = COLS * ROWS;
+    symbols.create_shape_instances(count);
+
+    let mut instances: Vec<ShapeInstance> = (0..count)
+        .map(|i| {
+            let (col, row) = (i % COLS, i / COLS);
+            let position = Vec2::new(ORIGIN + col as f32 * SPACING, ORIGIN + row as f32 * SPACING);
+            let color = Color::from_rgb(col as f32 / COLS as f32, 0.6, row as f32 / ROWS as f32);
+            ShapeInstance::new(position, &color)
+        })
+        .collect();
+
+    let mut app = App::new(window);
+
+    app.on_render(move || {
+        let t = renderer.get_time() as f32;
+
+        // each symbol turns to its own heading, and pulses slightly
+        for (i, instance) in instances.iter_mut().enumerate() {
+            let phase = i as f32 / count as f32 * TAU;
+            instance.rotation = t + phase * 7.0;
+            let s = 1.0 + 0.25 * (t * 3.0 + phase).sin();
+            instance.scale = Vec2::new(s, s);
+        }
+
+        symbols.set_instances(&instances);
+        symbols.render(&renderer);
+    });
+
+    app.run();
+}
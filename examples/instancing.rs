@@ -29,6 +29,7 @@ fn main() {
             fill: Some(Color::from_rgb(STEEL_BLUE.0, STEEL_BLUE.1, STEEL_BLUE.2)),
             stroke_color: None,
             stroke_width: None,
+            ..ShapeStyle::default()
         },
     );
     let instance_count = COLS * ROWS;
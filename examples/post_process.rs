@@ -0,0 +1,74 @@
+extern crate sky_renderer;
+
+use sky_renderer::core::{App, Color, PostEffect, PostProcess, Renderable, Renderer, Window};
+use sky_renderer::graphics2d::shapes::{Circle, ShapeKind, ShapeRenderable, ShapeStyle};
+
+fn fill_style(color: Color) -> ShapeStyle {
+    ShapeStyle {
+        fill: Some(color),
+        stroke_color: None,
+        stroke_width: None,
+        ..ShapeStyle::default()
+    }
+}
+
+fn main() {
+    let window = Window::new("Post Processing", 800, 600);
+    let renderer = Renderer::new(window.handle());
+
+    // dim tracks, and one alerting in a bright color that the bloom picks up
+    let mut tracks: Vec<ShapeRenderable> = (0..12)
+        .map(|i| {
+            let (x, y) = (
+                100.0 + (i % 4) as f32 * 200.0,
+                150.0 + (i / 4) as f32 * 150.0,
+            );
+            ShapeRenderable::from_shape(
+                x,
+                y,
+                ShapeKind::Circle(Circle::new(12.0)),
+                fill_style(Color::from_rgb(0.2, 0.4, 0.5)),
+            )
+        })
+        .collect();
+    let mut alert = ShapeRenderable::from_shape(
+        500.0,
+        300.0,
+        ShapeKind::Circle(Circle::new(12.0)),
+        fill_style(Color::from_rgb(1.0, 0.3, 0.2)),
+    );
+
+    let post_process = PostProcess::new(vec![
+        PostEffect::glow(),
+        PostEffect::ColorGrade {
+            brightness: 0.0,
+            contrast: 1.1,
+            saturation: 1.2,
+        },
+        PostEffect::vignette(0.6),
+    ])
+    .samples(4);
+    let effects = post_process.effects();
+
+    let mut app = App::new(window);
+    app.set_post_process(post_process);
+
+    app.on_update(move |frame| {
+        // pulse the glow
+        let pulse = 1.0 + (frame.elapsed as f32 * 4.0).sin();
+        effects.update(|effects| {
+            if let Some(PostEffect::Bloom { intensity, .. }) = effects.first_mut() {
+                *intensity = pulse;
+            }
+        });
+    });
+
+    app.on_render(move || {
+        for track in tracks.iter_mut() {
+            track.render(&renderer);
+        }
+        alert.render(&renderer);
+    });
+
+    app.run();
+}
@@ -0,0 +1,73 @@
+extern crate sky_renderer;
+
+use std::f32::consts::TAU;
+
+use sky_renderer::core::{App, Color, Persistence, Renderable, Renderer, Window};
+use sky_renderer::graphics2d::shapes::{Circle, Polyline, ShapeKind, ShapeRenderable, ShapeStyle};
+
+const CENTER: (f32, f32) = (400.0, 400.0);
+const RANGE: f32 = 350.0;
+/// Sweep speed, in radians per second.
+const SWEEP_SPEED: f32 = 1.5;
+
+fn main() {
+    let window = Window::new("Radar Persistence", 800, 800);
+    let renderer = Renderer::new(window.handle());
+
+    let green = Color::from_rgb(0.2, 1.0, 0.3);
+    let mut sweep = ShapeRenderable::from_shape(
+        CENTER.0,
+        CENTER.1,
+        ShapeKind::Polyline(Polyline::new(vec![(0.0, 0.0), (RANGE, 0.0)])),
+        ShapeStyle {
+            fill: None,
+            stroke_color: Some(green.clone()),
+            stroke_width: Some(3.0),
+            ..ShapeStyle::default()
+        },
+    );
+    let mut echo = ShapeRenderable::from_shape(
+        0.0,
+        0.0,
+        ShapeKind::Circle(Circle::new(6.0)),
+        ShapeStyle {
+            fill: Some(green),
+            stroke_color: None,
+            stroke_width: None,
+            ..ShapeStyle::default()
+        },
+    );
+    // (bearing, distance) of the targets
+    let targets = [(0.5, 200.0), (2.0, 120.0), (3.5, 300.0), (5.0, 250.0)];
+
+    let mut app = App::new(window);
+    app.set_clear_color(Color::black());
+    // the sweep leaves a trail that halves in brightness every 0.6 s
+    app.set_persistence(Persistence::with_half_life(0.6).samples(4));
+
+    let mut previous = 0.0f32;
+    app.on_render(move || {
+        let angle = (renderer.get_time() as f32 * SWEEP_SPEED) % TAU;
+        sweep.set_points(&[(0.0, 0.0), (RANGE * angle.cos(), -RANGE * angle.sin())]);
+        sweep.render(&renderer);
+
+        // echoes are painted once, when the sweep passes over them
+        for (bearing, distance) in targets {
+            let swept = if previous <= angle {
+                (previous..angle).contains(&bearing)
+            } else {
+                bearing >= previous || bearing < angle
+            };
+            if swept {
+                echo.set_position(
+                    CENTER.0 + distance * bearing.cos(),
+                    CENTER.1 - distance * bearing.sin(),
+                );
+                echo.render(&renderer);
+            }
+        }
+        previous = angle;
+    });
+
+    app.run();
+}
@@ -0,0 +1,56 @@
+extern crate sky_renderer;
+
+use sky_renderer::core::{App, Color, RenderTarget, Renderable, Renderer, Window};
+use sky_renderer::graphics2d::shapes::{Circle, Rectangle, ShapeKind, ShapeRenderable, ShapeStyle};
+
+fn fill_style(color: Color) -> ShapeStyle {
+    ShapeStyle {
+        fill: Some(color),
+        stroke_color: None,
+        stroke_width: None,
+        ..ShapeStyle::default()
+    }
+}
+
+fn main() {
+    let window = Window::new("Render Target", 800, 600);
+    let renderer = Renderer::new(window.handle());
+
+    // a multisampled offscreen canvas, drawn once per frame and shown twice
+    let target = RenderTarget::builder(300, 200).samples(4).build();
+
+    let mut background = ShapeRenderable::from_shape(
+        0.0,
+        0.0,
+        ShapeKind::Rectangle(Rectangle::new(300.0, 200.0)),
+        fill_style(Color::from_rgb(0.15, 0.2, 0.3)),
+    );
+    let mut ball = ShapeRenderable::from_shape(
+        0.0,
+        100.0,
+        ShapeKind::Circle(Circle::new(30.0)),
+        fill_style(Color::from_rgb(1.0, 0.6, 0.1)),
+    );
+
+    let mut preview = ShapeRenderable::from_render_target(20.0, 20.0, &target);
+    let mut enlarged = ShapeRenderable::from_render_target(340.0, 200.0, &target);
+    enlarged.set_size(440.0, 293.0);
+
+    let mut app = App::new(window);
+
+    app.on_render(move || {
+        let t = renderer.get_time() as f32;
+        ball.set_position(150.0 + 110.0 * t.sin(), 100.0);
+
+        renderer.with_target(&target, |r| {
+            r.clear(&Color::black());
+            background.render(r);
+            ball.render(r);
+        });
+
+        preview.render(&renderer);
+        enlarged.render(&renderer);
+    });
+
+    app.run();
+}
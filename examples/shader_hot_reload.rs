@@ -0,0 +1,53 @@
+extern crate sky_renderer;
+
+use std::path::PathBuf;
+use std::rc::Rc;
+use sky_renderer::core::{
+    App, Attribute, Geometry, Mesh, Renderer, Shader, ShaderSource, ShaderWatcher, Window,
+};
+use sky_renderer::core::engine::opengl::{GLfloat, GL_TRIANGLES};
+
+/// Edit examples/shaders/geometry.frag while this runs, the triangle updates on save.
+fn main() {
+    let window = Window::new("Shader Hot Reload", 800, 600);
+
+    let shader_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/shaders");
+
+    /* 5 values per vertex: x,y,r,g,b */
+    let vertices: Vec<GLfloat> = vec![
+        /* bottom right */ 0.5, -0.5, /*color */ 1.0, 0.0, 0.0,
+        /* bottom left */ -0.5, -0.5, /*color */ 0.0, 1.0, 0.0,
+        /* top */ 0.0, 0.5, /*color */ 0.0, 0.0, 1.0,
+    ];
+
+    let mut geometry = Geometry::new(GL_TRIANGLES);
+    geometry.add_buffer(&vertices, 5);
+    geometry.add_vertex_attribute(Attribute::new(0, 2, 5, 0));
+    geometry.add_vertex_attribute(Attribute::new(1, 3, 5, 2));
+
+    let shader = Shader::from_sources(
+        ShaderSource::File(shader_dir.join("geometry.vert")),
+        ShaderSource::File(shader_dir.join("geometry.frag")),
+        None,
+    )
+    .unwrap_or_else(|e| panic!("Failed to compile shader: {e}"));
+    let shader = Rc::new(shader);
+
+    let mut watcher = ShaderWatcher::new();
+    watcher.watch(&shader);
+
+    let mesh = Mesh::new(shader, geometry);
+
+    let renderer = Renderer::new(window.handle());
+    let mut app = App::new(window);
+
+    app.on_update(move |_| {
+        for error in watcher.poll() {
+            eprintln!("{error}");
+        }
+    });
+    app.on_render(move || {
+        renderer.draw_mesh(&mesh)
+    });
+    app.run();
+}
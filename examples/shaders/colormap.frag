@@ -0,0 +1,12 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D u_data;      // R32F values in [0, 1]
+uniform sampler2D u_colormap;  // 256x1 color lookup table
+
+void main()
+{
+    float value = texture(u_data, TexCoord).r;
+    FragColor = texture(u_colormap, vec2(value, 0.5));
+}
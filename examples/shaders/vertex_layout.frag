@@ -0,0 +1,11 @@
+#version 330 core
+in vec4 ourColor;
+flat in uint id;
+out vec4 FragColor;
+
+uniform int u_highlighted;
+
+void main()
+{
+    FragColor = int(id) == u_highlighted ? vec4(1.0) : ourColor;
+}
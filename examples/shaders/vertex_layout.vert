@@ -0,0 +1,13 @@
+#version 330 core
+layout (location = 0) in vec2 aPos;
+layout (location = 1) in vec4 aColor;   // 4 normalized bytes
+layout (location = 2) in uint aId;      // integer attribute
+out vec4 ourColor;
+flat out uint id;
+
+void main()
+{
+    gl_Position = vec4(aPos, 0.0, 1.0);
+    ourColor = aColor;
+    id = aId;
+}
@@ -42,6 +42,7 @@ fn stroke_style(color: Color, width: f32) -> ShapeStyle {
         fill: Some(color.clone()),
         stroke_color: Some(color),
         stroke_width: Some(width),
+        ..ShapeStyle::default()
     }
 }
 
@@ -50,6 +51,7 @@ fn fill_style(color: Color) -> ShapeStyle {
         fill: Some(color),
         stroke_color: None,
         stroke_width: None,
+        ..ShapeStyle::default()
     }
 }
 
@@ -194,10 +196,17 @@ fn main() {
         .expect("Failed to write SVG");
     */
 
+    let mut last_report = 0.0;
     app.on_render(move || {
         for shape in &mut shapes {
             shape.render(&renderer);
         }
+
+        // report the GL work of the previous frame every few seconds
+        if renderer.get_time() - last_report > 5.0 {
+            last_report = renderer.get_time();
+            println!("{:?}", renderer.frame_stats());
+        }
     });
     app.run();
 }
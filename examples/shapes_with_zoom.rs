@@ -1,6 +1,6 @@
 extern crate sky_renderer;
 
-use sky_renderer::core::{App, Color, Renderable, Renderer, Window};
+use sky_renderer::core::{App, Color, Event, Renderable, Renderer, Window};
 use sky_renderer::graphics2d::shapes::{
     Circle, Ellipse, Line, MultiPoint, Polygon, Polyline, Rectangle, RoundedRectangle, ShapeKind,
     ShapeRenderable, ShapeStyle,
@@ -8,15 +8,12 @@ use sky_renderer::graphics2d::shapes::{
 
 use std::cell::Cell;
 
-thread_local! {
-    static ZOOM_LEVEL: Cell<f32> = Cell::new(1.0);
-}
-
 fn stroke_style(color: Color, width: f32) -> ShapeStyle {
     ShapeStyle {
         fill: Some(color.clone()),
         stroke_color: Some(color),
         stroke_width: Some(width),
+        ..ShapeStyle::default()
     }
 }
 
@@ -25,31 +22,35 @@ fn fill_style(color: Color) -> ShapeStyle {
         fill: Some(color),
         stroke_color: None,
         stroke_width: None,
+        ..ShapeStyle::default()
     }
 }
 
 fn main() {
-    let mut window = Window::new("Shapes", 800, 800);
+    let window = Window::new("Shapes", 800, 800);
     let mut renderer = Renderer::new(window.handle());
     renderer.set_point_size(6.0);
 
-    window.on_scroll(move |_, y_offset| {
-        let zoom_step = 1.1;
-        let zoom_factor = if y_offset > 0.0 {
-            zoom_step
-        } else {
-            1.0 / zoom_step
-        };
+    let zoom_level = Cell::new(1.0f32);
+    let zoom = &zoom_level;
+
+    let mut app = App::new(window);
+
+    app.on_event(move |event| {
+        if let Event::Scroll { y_offset, .. } = *event {
+            let zoom_step = 1.1;
+            let zoom_factor = if y_offset > 0.0 {
+                zoom_step
+            } else {
+                1.0 / zoom_step
+            };
 
-        ZOOM_LEVEL.with(|z| {
-            let new_zoom = (z.get() * zoom_factor).clamp(0.1, 10.0);
-            z.set(new_zoom);
+            let new_zoom = (zoom.get() * zoom_factor).clamp(0.1, 10.0);
+            zoom.set(new_zoom);
             println!("zoom level: {}", new_zoom);
-        });
+        }
     });
 
-    let mut app = App::new(window);
-
     // Polyline points (relative to first point)
     let polyline_points = vec![
         (0.0, 0.0),
@@ -159,10 +160,8 @@ fn main() {
     ];
 
     app.on_render(move || {
+        renderer.zoom_level = zoom.get();
         for shape in &mut shapes {
-            ZOOM_LEVEL.with(|z| {
-                renderer.zoom_level = z.get();
-            });
             shape.render(&renderer);
         }
     });
@@ -39,7 +39,7 @@ fn main() {
     let fragment_shader_source = include_str!("shaders/transform.frag");
 
     let shader = Shader::compile(vertex_shader_source, fragment_shader_source, None)
-        .expect("Failed to compile shader");
+        .unwrap_or_else(|e| panic!("Failed to compile shader: {e}"));
 
     let mut mesh = Mesh::new(Rc::new(shader), geometry);
     let renderer = Renderer::new(window.handle());
@@ -39,7 +39,7 @@ fn main() {
     geometry.add_vertex_attribute(color_attribute);
 
     let shader = Shader::compile(vertex_shader_source, fragment_shader_source, None)
-        .expect("Failed to compile shader");
+        .unwrap_or_else(|e| panic!("Failed to compile shader: {e}"));
 
     let mesh = Mesh::new(Rc::new(shader), geometry);
 
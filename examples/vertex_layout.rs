@@ -0,0 +1,67 @@
+extern crate sky_renderer;
+
+use sky_renderer::core::engine::opengl::GL_TRIANGLES;
+use sky_renderer::core::{App, Geometry, Mesh, Renderer, Shader, Window};
+use sky_renderer::impl_vertex;
+use std::rc::Rc;
+
+/// Position, a color packed in 4 bytes and an integer id, e.g. for picking.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct ColoredVertex {
+    position: [f32; 2],
+    color: [u8; 4],
+    id: u32,
+}
+
+impl_vertex!(ColoredVertex {
+    position => 0,
+    color => 1 normalized,
+    id => 2,
+});
+
+fn vertex(x: f32, y: f32, color: [u8; 4], id: u32) -> ColoredVertex {
+    ColoredVertex {
+        position: [x, y],
+        color,
+        id,
+    }
+}
+
+fn main() {
+    let window = Window::new("Vertex Layout", 800, 600);
+
+    let orange = [255, 140, 0, 255];
+    let teal = [0, 128, 128, 255];
+    let vertices = [
+        // left triangle, id 1
+        vertex(-0.9, -0.5, orange, 1),
+        vertex(-0.1, -0.5, orange, 1),
+        vertex(-0.5, 0.5, orange, 1),
+        // right triangle, id 2
+        vertex(0.1, -0.5, teal, 2),
+        vertex(0.9, -0.5, teal, 2),
+        vertex(0.5, 0.5, teal, 2),
+    ];
+    let geometry = Geometry::from_vertices(GL_TRIANGLES, &vertices);
+
+    let shader = Shader::compile(
+        include_str!("shaders/vertex_layout.vert"),
+        include_str!("shaders/vertex_layout.frag"),
+        None,
+    )
+    .unwrap_or_else(|e| panic!("Failed to compile shader: {e}"));
+
+    let mut mesh = Mesh::new(Rc::new(shader), geometry);
+
+    let renderer = Renderer::new(window.handle());
+    let mut app = App::new(window);
+
+    app.on_render(move || {
+        // highlight each triangle in turn by its id
+        let highlighted = 1 + (renderer.get_time() as i32) % 2;
+        mesh.set_uniform("u_highlighted", highlighted);
+        renderer.draw_mesh(&mesh)
+    });
+    app.run();
+}
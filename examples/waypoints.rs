@@ -2,6 +2,7 @@ extern crate sky_renderer;
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use glam::Vec4;
 use sky_renderer::core::{App, Attribute, Geometry, Mesh, Renderer, Shader, Window};
 use sky_renderer::core::engine::opengl::{GL_POINTS};
 
@@ -47,14 +48,14 @@ fn main() {
         fragment_shader_source,
         Some(geometry_shader_source),
     )
-    .expect("Failed to compile shader");
+    .unwrap_or_else(|e| panic!("Failed to compile shader: {e}"));
 
     let mut geometry = Geometry::new(GL_POINTS);
 
     geometry.add_buffer(&wgs84_coordinates, 2);
     geometry.add_vertex_attribute(Attribute::new(0, 2, 2usize, 0));
 
-    let mesh = Mesh::new(Rc::new(shader), geometry);
+    let mut mesh = Mesh::new(Rc::new(shader), geometry);
 
     let renderer = Renderer::new(window.handle());
     renderer.set_point_size(5.0);
@@ -64,7 +65,7 @@ fn main() {
 
     app.on_render(move || {
         MAP_BOUNDS.with(|bounds| {
-            mesh.set_uniform_4f("map_bounds", &bounds.borrow());
+            mesh.set_uniform("map_bounds", Vec4::from_array(*bounds.borrow()));
         });
         renderer.draw_mesh(&mesh);
     });
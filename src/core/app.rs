@@ -1,18 +1,159 @@
-use crate::core::Window;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::core::engine::glfw::{glfw_get_time, glfw_post_empty_event};
+use crate::core::feed::{DataFeed, DataSender};
+use crate::core::{Color, Event, Persistence, PostProcess, Window};
+
+/// Upper bound on fixed updates per frame, so a slow frame can't snowball into ever more
+/// catch-up steps. Time beyond it is dropped.
+const MAX_FIXED_STEPS_PER_FRAME: u32 = 8;
+
+/// Default fixed timestep used by [`App::on_fixed_update`], in seconds.
+pub const DEFAULT_FIXED_TIMESTEP: f64 = 1.0 / 60.0;
+
+/// Longest time [`RedrawPolicy::OnDemand`] blocks before re-checking whether the window
+/// should close.
+const ON_DEMAND_WAIT_TIMEOUT: f64 = 0.5;
+
+/// Controls when [`App::run`] renders a new frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedrawPolicy {
+    /// Render frames back to back, as fast as buffer swaps allow.
+    #[default]
+    Continuous,
+    /// Sleep until an input event arrives or a redraw is requested through
+    /// [`App::request_redraw`] or a [`WakeHandle`].
+    OnDemand,
+    /// Render continuously, but at most `n` frames per second, sleeping in between. `n`
+    /// must be at least 1.
+    MaxFps(u32),
+}
+
+/// Thread-safe handle to request a redraw of an [`App`], waking its loop if it is asleep.
+#[derive(Debug, Clone)]
+pub struct WakeHandle {
+    redraw_requested: Arc<AtomicBool>,
+}
+
+impl WakeHandle {
+    /// Schedules a new frame. Can be called from any thread.
+    pub fn request_redraw(&self) {
+        self.redraw_requested.store(true, Ordering::Release);
+        glfw_post_empty_event();
+    }
+}
+
+/// Timing information passed to the update callbacks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameInfo {
+    /// Number of frames rendered before this one.
+    pub frame: u64,
+    /// Seconds since the previous frame, or the fixed timestep in fixed updates.
+    pub dt: f64,
+    /// Seconds since [`App::run`] started, or the simulated time in fixed updates.
+    pub elapsed: f64,
+}
+
+type FrameCallback<'a> = Box<dyn FnMut(&FrameInfo) + 'a>;
+type EventCallback<'a> = Box<dyn FnMut(&Event) + 'a>;
 
 pub struct App<'a> {
     pub window: Box<Window>,
+    clear_color: Color,
+    post_process: Option<PostProcess>,
+    persistence: Option<Persistence>,
+    fixed_timestep: f64,
+    redraw_policy: RedrawPolicy,
+    redraw_requested: Arc<AtomicBool>,
     render_callback: Option<Box<dyn FnMut() + 'a>>,
+    update_callback: Option<FrameCallback<'a>>,
+    fixed_update_callback: Option<FrameCallback<'a>>,
+    event_callback: Option<EventCallback<'a>>,
+    feeds: Vec<Box<dyn FnMut() + 'a>>,
+    exit_callback: Option<Box<dyn FnOnce() + 'a>>,
 }
 
 impl<'a> App<'a> {
     pub fn new(window: Box<Window>) -> Self {
         Self {
             window,
+            clear_color: Color::from_rgb(0.07, 0.13, 0.17),
+            post_process: None,
+            persistence: None,
+            fixed_timestep: DEFAULT_FIXED_TIMESTEP,
+            redraw_policy: RedrawPolicy::Continuous,
+            redraw_requested: Arc::new(AtomicBool::new(true)),
             render_callback: None,
+            update_callback: None,
+            fixed_update_callback: None,
+            event_callback: None,
+            feeds: Vec::new(),
+            exit_callback: None,
+        }
+    }
+
+    /// Sets the color the window is cleared to before each frame is rendered.
+    pub fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = color;
+    }
+
+    /// Renders each frame offscreen and runs it through `post_process` before showing it.
+    /// Keep a [`PostProcess::effects`] handle to change the effects while running.
+    pub fn set_post_process(&mut self, post_process: PostProcess) {
+        self.post_process = Some(post_process);
+    }
+
+    /// Renders frames directly to the window again.
+    pub fn clear_post_process(&mut self) {
+        self.post_process = None;
+    }
+
+    /// Lets what is rendered fade out over time instead of being cleared every frame, see
+    /// [`Persistence`]. Applied before the post-processing, if any.
+    ///
+    /// With [`RedrawPolicy::OnDemand`], frames keep being rendered after each requested one
+    /// until what it drew has faded out. Those frames only fade the accumulated content:
+    /// the updates run, but the render callback isn't called.
+    pub fn set_persistence(&mut self, persistence: Persistence) {
+        self.persistence = Some(persistence);
+    }
+
+    /// Clears frames before rendering them again, discarding the accumulated content.
+    pub fn clear_persistence(&mut self) {
+        self.persistence = None;
+    }
+
+    /// Sets the step, in seconds, of the updates registered with [`App::on_fixed_update`].
+    pub fn set_fixed_timestep(&mut self, step: f64) {
+        assert!(step > 0.0, "Fixed timestep must be positive");
+        self.fixed_timestep = step;
+    }
+
+    /// Sets when frames are rendered, see [`RedrawPolicy`].
+    ///
+    /// With [`RedrawPolicy::OnDemand`], `dt` in [`FrameInfo`] is the time since the last
+    /// rendered frame, which may span a long idle period.
+    pub fn set_redraw_policy(&mut self, policy: RedrawPolicy) {
+        debug_assert!(
+            policy != RedrawPolicy::MaxFps(0),
+            "RedrawPolicy::MaxFps needs at least 1 frame per second"
+        );
+        self.redraw_policy = policy;
+    }
+
+    /// Schedules a new frame when using [`RedrawPolicy::OnDemand`].
+    pub fn request_redraw(&self) {
+        self.wake_handle().request_redraw();
+    }
+
+    /// Returns a handle that other threads (or callbacks) can use to request a redraw.
+    pub fn wake_handle(&self) -> WakeHandle {
+        WakeHandle {
+            redraw_requested: Arc::clone(&self.redraw_requested),
         }
     }
-    
+
     pub fn on_render<F>(&mut self, callback: F)
     where
         F: FnMut() + 'a,
@@ -20,16 +161,180 @@ impl<'a> App<'a> {
         self.render_callback = Some(Box::new(callback));
     }
 
+    /// Registers a callback invoked once per frame, before rendering, with the time elapsed
+    /// since the previous frame.
+    pub fn on_update<F>(&mut self, callback: F)
+    where
+        F: FnMut(&FrameInfo) + 'a,
+    {
+        self.update_callback = Some(Box::new(callback));
+    }
+
+    /// Registers a callback invoked at a fixed rate (see [`App::set_fixed_timestep`]),
+    /// zero or more times per frame, before [`App::on_update`]. Use it for simulations
+    /// that must not depend on the frame rate.
+    pub fn on_fixed_update<F>(&mut self, callback: F)
+    where
+        F: FnMut(&FrameInfo) + 'a,
+    {
+        self.fixed_update_callback = Some(Box::new(callback));
+    }
+
+    /// Registers a callback invoked for each window and input event, once per frame before
+    /// the updates.
+    pub fn on_event<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Event) + 'a,
+    {
+        self.event_callback = Some(Box::new(callback));
+    }
+
+    /// Creates a channel for feeding data to the render loop from other threads.
+    ///
+    /// Pass the returned [`DataFeed`] to [`App::attach_receiver`] and move the
+    /// [`DataSender`] to the producing thread; every message sent wakes the loop.
+    pub fn data_feed<T>(&self) -> (DataSender<T>, DataFeed<T>) {
+        DataFeed::new(self.wake_handle())
+    }
+
+    /// Registers `on_data` to receive the messages pending on `feed`, once per frame on the
+    /// render thread, after events and before the updates. It is only called when at least
+    /// one message arrived since the previous frame.
+    pub fn attach_receiver<T, F>(&mut self, feed: DataFeed<T>, mut on_data: F)
+    where
+        T: 'a,
+        F: FnMut(Vec<T>) + 'a,
+    {
+        self.feeds.push(Box::new(move || {
+            let batch = feed.drain();
+            if !batch.is_empty() {
+                on_data(batch);
+            }
+        }));
+    }
+
+    /// Registers a callback invoked once the window has been closed and the loop exits.
+    pub fn on_exit<F>(&mut self, callback: F)
+    where
+        F: FnOnce() + 'a,
+    {
+        self.exit_callback = Some(Box::new(callback));
+    }
+
     pub fn run(mut self) {
+        let start = glfw_get_time();
+        let mut last = start;
+        let mut frame: u64 = 0;
+        let mut accumulator = 0.0;
+        let mut simulated = 0.0;
+        let mut fade_until = f64::NEG_INFINITY;
+
         while !self.window.window_should_close() {
-            self.window.clear_color(0.07, 0.13, 0.17, 1.0);
+            // frame rendered only to fade the persisted content, without drawing anything new
+            let mut fade_only = false;
+            if self.redraw_policy == RedrawPolicy::OnDemand {
+                if self.should_redraw() {
+                    // keep rendering while what this frame draws fades out
+                    let fade = self
+                        .persistence
+                        .as_ref()
+                        .map_or(0.0, Persistence::fade_duration);
+                    fade_until = glfw_get_time() + fade;
+                } else if glfw_get_time() >= fade_until {
+                    self.window.wait_events_timeout(ON_DEMAND_WAIT_TIMEOUT);
+                    continue;
+                } else {
+                    fade_only = true;
+                }
+            }
+            self.redraw_requested.store(false, Ordering::Release);
+
+            let now = glfw_get_time();
+            let info = FrameInfo {
+                frame,
+                dt: now - last,
+                elapsed: now - start,
+            };
+            last = now;
+
+            for event in self.window.drain_events() {
+                if let Some(cb) = self.event_callback.as_mut() {
+                    cb(&event);
+                }
+            }
+
+            for feed in self.feeds.iter_mut() {
+                feed();
+            }
+
+            if let Some(cb) = self.fixed_update_callback.as_mut() {
+                accumulator += info.dt;
+                let mut steps = 0;
+                while accumulator >= self.fixed_timestep && steps < MAX_FIXED_STEPS_PER_FRAME {
+                    simulated += self.fixed_timestep;
+                    cb(&FrameInfo {
+                        frame,
+                        dt: self.fixed_timestep,
+                        elapsed: simulated,
+                    });
+                    accumulator -= self.fixed_timestep;
+                    steps += 1;
+                }
+                if steps == MAX_FIXED_STEPS_PER_FRAME {
+                    accumulator = accumulator.min(self.fixed_timestep);
+                }
+            }
 
-            if let Some(cb) = self.render_callback.as_mut() {
-                cb();
+            if let Some(cb) = self.update_callback.as_mut() {
+                cb(&info);
+            }
+
+            let render_callback = &mut self.render_callback;
+            let persistence = &mut self.persistence;
+            let mut render = || {
+                let mut draw = || {
+                    if let Some(cb) = render_callback.as_mut()
+                        && !fade_only
+                    {
+                        cb();
+                    }
+                };
+                match persistence.as_mut() {
+                    Some(persistence) => persistence.render(info.dt, draw),
+                    None => draw(),
+                }
+            };
+            if let Some(post_process) = self.post_process.as_mut() {
+                post_process.render(&self.clear_color, render);
+            } else {
+                let c = &self.clear_color;
+                self.window
+                    .clear_color(c.red_value(), c.green_value(), c.blue_value(), c.alpha());
+                render();
             }
 
             self.window.swap_buffers();
             self.window.poll_events();
+
+            if let RedrawPolicy::MaxFps(fps) = self.redraw_policy {
+                let deadline = now + 1.0 / fps.max(1) as f64;
+                loop {
+                    let remaining = deadline - glfw_get_time();
+                    if remaining <= 0.0 || self.window.window_should_close() {
+                        break;
+                    }
+                    self.window.wait_events_timeout(remaining);
+                }
+            }
+            frame += 1;
         }
+
+        if let Some(cb) = self.exit_callback.take() {
+            cb();
+        }
+    }
+
+    fn should_redraw(&self) -> bool {
+        self.redraw_requested.load(Ordering::Acquire) || self.window.has_pending_events()
     }
-}
\ No newline at end of file
+}
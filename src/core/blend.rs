@@ -0,0 +1,167 @@
+use crate::core::engine::opengl::{
+    GL_DST_COLOR, GL_FUNC_ADD, GL_ONE, GL_ONE_MINUS_SRC_ALPHA, GL_ONE_MINUS_SRC_COLOR,
+    GL_SRC_ALPHA, GL_SRC_COLOR, GL_ZERO, GLenum,
+};
+
+/// How a mesh's colors are combined with what is already drawn.
+///
+/// The alpha channel is always accumulated as with [`BlendMode::Premultiplied`], so that
+/// render targets cleared to transparent can be composited afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Regular transparency, for colors with straight (non-premultiplied) alpha.
+    #[default]
+    Alpha,
+    /// Transparency for colors already multiplied by their alpha, e.g. render target
+    /// textures.
+    Premultiplied,
+    /// Adds the colors, weighted by their alpha: overlapping shapes get brighter, as for
+    /// dense track clouds or heat overlays.
+    Additive,
+    /// Multiplies the colors, darkening what is below.
+    Multiply,
+    /// Inverse of [`BlendMode::Multiply`], brightening what is below.
+    Screen,
+}
+
+impl BlendMode {
+    /// Whether the blend factors expect colors multiplied by their alpha, which shapes
+    /// then premultiply in their shader, see [`ShapeRenderable::set_blend_mode`].
+    ///
+    /// [`ShapeRenderable::set_blend_mode`]: crate::graphics2d::shapes::ShapeRenderable::set_blend_mode
+    pub fn expects_premultiplied(self) -> bool {
+        matches!(
+            self,
+            BlendMode::Premultiplied | BlendMode::Multiply | BlendMode::Screen
+        )
+    }
+
+    pub(crate) fn func(self) -> BlendFunc {
+        let (src_rgb, dst_rgb) = match self {
+            BlendMode::Alpha => (GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA),
+            BlendMode::Premultiplied => (GL_ONE, GL_ONE_MINUS_SRC_ALPHA),
+            BlendMode::Additive => (GL_SRC_ALPHA, GL_ONE),
+            BlendMode::Multiply => (GL_DST_COLOR, GL_ONE_MINUS_SRC_ALPHA),
+            BlendMode::Screen => (GL_ONE, GL_ONE_MINUS_SRC_COLOR),
+        };
+        BlendFunc {
+            equation: GL_FUNC_ADD,
+            src_rgb,
+            dst_rgb,
+            src_alpha: GL_ONE,
+            dst_alpha: GL_ONE_MINUS_SRC_ALPHA,
+        }
+    }
+}
+
+/// The blend equation and factors, as passed to `glBlendEquation` and
+/// `glBlendFuncSeparate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlendFunc {
+    pub equation: GLenum,
+    pub src_rgb: GLenum,
+    pub dst_rgb: GLenum,
+    pub src_alpha: GLenum,
+    pub dst_alpha: GLenum,
+}
+
+impl BlendFunc {
+    /// Multiplies the destination by the source color, channel by channel.
+    pub(crate) const MODULATE: BlendFunc = BlendFunc {
+        equation: GL_FUNC_ADD,
+        src_rgb: GL_ZERO,
+        dst_rgb: GL_SRC_COLOR,
+        src_alpha: GL_ZERO,
+        dst_alpha: GL_SRC_ALPHA,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODES: [BlendMode; 5] = [
+        BlendMode::Alpha,
+        BlendMode::Premultiplied,
+        BlendMode::Additive,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+    ];
+
+    fn factor(factor: GLenum, src: [f32; 4], dst: [f32; 4], channel: usize) -> f32 {
+        match factor {
+            GL_ZERO => 0.0,
+            GL_ONE => 1.0,
+            GL_SRC_COLOR => src[channel],
+            GL_ONE_MINUS_SRC_COLOR => 1.0 - src[channel],
+            GL_DST_COLOR => dst[channel],
+            GL_SRC_ALPHA => src[3],
+            GL_ONE_MINUS_SRC_ALPHA => 1.0 - src[3],
+            _ => panic!("unexpected blend factor {factor:#x}"),
+        }
+    }
+
+    /// Blends the straight alpha `color` over `dst` in `mode`, as a shape would be.
+    fn blend(mode: BlendMode, color: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let func = mode.func();
+        assert_eq!(func.equation, GL_FUNC_ADD);
+        let mut src = color;
+        if mode.expects_premultiplied() {
+            for channel in &mut src[..3] {
+                *channel *= color[3];
+            }
+        }
+        std::array::from_fn(|channel| {
+            let (src_factor, dst_factor) = if channel < 3 {
+                (func.src_rgb, func.dst_rgb)
+            } else {
+                (func.src_alpha, func.dst_alpha)
+            };
+            src[channel] * factor(src_factor, src, dst, channel)
+                + dst[channel] * factor(dst_factor, src, dst, channel)
+        })
+    }
+
+    fn assert_close(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() < 1e-6,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn transparent_leaves_destination_unchanged() {
+        let dst = [0.2, 0.5, 0.8, 0.6];
+        for mode in MODES {
+            for color in [[1.0, 1.0, 1.0, 0.0], [0.3, 0.7, 0.1, 0.0]] {
+                assert_close(blend(mode, color, dst), dst);
+            }
+        }
+    }
+
+    #[test]
+    fn opaque_results() {
+        let dst = [0.2, 0.5, 0.8, 1.0];
+        let src = [0.5, 0.5, 0.5, 1.0];
+        assert_close(blend(BlendMode::Alpha, src, dst), src);
+        assert_close(blend(BlendMode::Premultiplied, src, dst), src);
+        assert_close(blend(BlendMode::Additive, src, dst), [0.7, 1.0, 1.3, 1.0]);
+        assert_close(blend(BlendMode::Multiply, src, dst), [0.1, 0.25, 0.4, 1.0]);
+        assert_close(blend(BlendMode::Screen, src, dst), [0.6, 0.75, 0.9, 1.0]);
+    }
+
+    #[test]
+    fn translucent_multiply_and_screen_interpolate() {
+        let dst = [0.4, 0.4, 0.4, 1.0];
+        let white = [1.0, 1.0, 1.0, 0.5];
+        let black = [0.0, 0.0, 0.0, 0.5];
+        // multiplying by white or screening black is a no-op at any opacity
+        assert_close(blend(BlendMode::Multiply, white, dst), dst);
+        assert_close(blend(BlendMode::Screen, black, dst), dst);
+        // and half way to the opaque result otherwise
+        assert_close(blend(BlendMode::Multiply, black, dst), [0.2, 0.2, 0.2, 1.0]);
+        assert_close(blend(BlendMode::Screen, white, dst), [0.7, 0.7, 0.7, 1.0]);
+    }
+}
@@ -11,6 +11,11 @@ impl Color{
         Color {r, g, b, a:1.0}
     }
 
+    /// A color with straight (non-premultiplied) alpha, see [`crate::core::BlendMode`].
+    pub fn from_rgba(r:f32, g:f32, b:f32, a:f32)->Self{
+        Color {r, g, b, a}
+    }
+
     pub fn red_value(&self)->f32{
         self.r
     }
@@ -3,6 +3,7 @@ use std::ffi::c_void;
 use std::os::raw::c_char;
 use std::os::raw::c_double;
 use std::os::raw::c_int;
+use std::os::raw::c_uint;
 
 pub const GLFW_SAMPLES: i32 = 0x0002100D;
 
@@ -14,6 +15,49 @@ pub const GLFW_PLATFORM_NULL: i32 = 0x00060005;
 
 pub enum GLFWwindow {}
 
+/// Window creation options passed to `_glfwCreateWindowWithConfig`.
+///
+/// Mirrors the `WindowConfig` struct declared in `cpp/glrenderer.h`; boolean fields
+/// use `GLFW_TRUE`/`GLFW_FALSE` (1/0).
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct WindowConfig {
+    pub samples: c_int,
+    pub context_version_major: c_int,
+    pub context_version_minor: c_int,
+    pub resizable: c_int,
+    pub decorated: c_int,
+    pub visible: c_int,
+    pub has_position: c_int,
+    pub pos_x: c_int,
+    pub pos_y: c_int,
+    /// `-1` for windowed mode, otherwise the index of the monitor to go fullscreen on.
+    pub monitor: c_int,
+    /// `-1` leaves the driver default.
+    pub swap_interval: c_int,
+    /// Uses the null platform with an OSMesa context instead of a display server.
+    pub headless: c_int,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            samples: 4,
+            context_version_major: 3,
+            context_version_minor: 3,
+            resizable: 1,
+            decorated: 1,
+            visible: 1,
+            has_position: 0,
+            pos_x: 0,
+            pos_y: 0,
+            monitor: -1,
+            swap_interval: -1,
+            headless: 0,
+        }
+    }
+}
+
 pub type GLFWframebuffersizefun =
     Option<extern "C" fn(window: *const GLFWwindow, width: i32, height: i32)>;
 
@@ -25,6 +69,23 @@ pub type GLFWscrollfun =
 
 pub type GLFWcursorposfun = Option<extern "C" fn(window: *const GLFWwindow, xpos: f64, ypos: f64)>;
 
+pub type GLFWmousebuttonfun =
+    Option<extern "C" fn(window: *const GLFWwindow, button: c_int, action: c_int, mods: c_int)>;
+
+pub type GLFWkeyfun = Option<
+    extern "C" fn(window: *const GLFWwindow, key: c_int, scancode: c_int, action: c_int, mods: c_int),
+>;
+
+pub type GLFWcharfun = Option<extern "C" fn(window: *const GLFWwindow, codepoint: c_uint)>;
+
+pub type GLFWwindowfocusfun = Option<extern "C" fn(window: *const GLFWwindow, focused: c_int)>;
+
+pub type GLFWwindowclosefun = Option<extern "C" fn(window: *const GLFWwindow)>;
+
+pub type GLFWdropfun = Option<
+    extern "C" fn(window: *const GLFWwindow, path_count: c_int, paths: *const *const c_char),
+>;
+
 unsafe extern "C" {
     fn _glfwCreateWindow(
         title: *const c_char,
@@ -33,6 +94,14 @@ unsafe extern "C" {
         callback: GLFWframebuffersizefun,
     ) -> *const GLFWwindow;
 
+    fn _glfwCreateWindowWithConfig(
+        title: *const c_char,
+        width: c_int,
+        height: c_int,
+        config: *const WindowConfig,
+        callback: GLFWframebuffersizefun,
+    ) -> *const GLFWwindow;
+
     fn _glfwGetWindowContentScale(window: *const GLFWwindow, xscale: *mut c_float, yscale: *mut c_float);
 
     fn _glfwWindowHint(hint: c_int, value:c_int);
@@ -43,8 +112,12 @@ unsafe extern "C" {
     fn _glfwWindowShouldClose(window: *const GLFWwindow) -> c_int;
     fn _glfwDestroyWindow(window: *const GLFWwindow);
     fn _glfwSwapBuffers(window: *const GLFWwindow);
+    fn _glfwSwapInterval(interval: c_int);
 
     fn _glfwPollEvents();
+    fn _glfwWaitEvents();
+    fn _glfwWaitEventsTimeout(timeout: c_double);
+    fn _glfwPostEmptyEvent();
     fn _glfwTerminate();
 
     fn _glfwGetTime() -> c_double;
@@ -52,6 +125,12 @@ unsafe extern "C" {
     fn _glfwSetWindowSizeCallback(window: *const GLFWwindow, callback: GLFWwindowsizefun);
     fn _glfwSetScrollCallback(window: *const GLFWwindow, callback: GLFWscrollfun);
     fn _glfwSetCursorPosCallback(window: *const GLFWwindow, callback: GLFWcursorposfun);
+    fn _glfwSetMouseButtonCallback(window: *const GLFWwindow, callback: GLFWmousebuttonfun);
+    fn _glfwSetKeyCallback(window: *const GLFWwindow, callback: GLFWkeyfun);
+    fn _glfwSetCharCallback(window: *const GLFWwindow, callback: GLFWcharfun);
+    fn _glfwSetWindowFocusCallback(window: *const GLFWwindow, callback: GLFWwindowfocusfun);
+    fn _glfwSetWindowCloseCallback(window: *const GLFWwindow, callback: GLFWwindowclosefun);
+    fn _glfwSetDropCallback(window: *const GLFWwindow, callback: GLFWdropfun);
     fn _glfwGetWindowSize(window: *const GLFWwindow, width: *mut c_int, height: *mut c_int);
 
     fn _glfwGetPlatform() -> c_int;
@@ -75,6 +154,17 @@ pub fn glfw_create_window(
     window_pointer
 }
 
+pub fn glfw_create_window_with_config(
+    title: &str,
+    width: i32,
+    height: i32,
+    config: &WindowConfig,
+    callback: GLFWframebuffersizefun,
+) -> *const GLFWwindow {
+    let title_c_string = CString::new(title).expect("Failed to create title");
+    unsafe { _glfwCreateWindowWithConfig(title_c_string.as_ptr(), width, height, config, callback) }
+}
+
 pub fn glfw_get_window_content_scale(window: *const GLFWwindow)->(f32, f32){
     unsafe {
         let mut xs: f32 = 0.0;
@@ -120,6 +210,42 @@ pub fn glfw_set_cursor_pos_callback(window: *const GLFWwindow, callback: GLFWcur
     }
 }
 
+pub fn glfw_set_mouse_button_callback(window: *const GLFWwindow, callback: GLFWmousebuttonfun) {
+    unsafe {
+        _glfwSetMouseButtonCallback(window, callback);
+    }
+}
+
+pub fn glfw_set_key_callback(window: *const GLFWwindow, callback: GLFWkeyfun) {
+    unsafe {
+        _glfwSetKeyCallback(window, callback);
+    }
+}
+
+pub fn glfw_set_char_callback(window: *const GLFWwindow, callback: GLFWcharfun) {
+    unsafe {
+        _glfwSetCharCallback(window, callback);
+    }
+}
+
+pub fn glfw_set_window_focus_callback(window: *const GLFWwindow, callback: GLFWwindowfocusfun) {
+    unsafe {
+        _glfwSetWindowFocusCallback(window, callback);
+    }
+}
+
+pub fn glfw_set_window_close_callback(window: *const GLFWwindow, callback: GLFWwindowclosefun) {
+    unsafe {
+        _glfwSetWindowCloseCallback(window, callback);
+    }
+}
+
+pub fn glfw_set_drop_callback(window: *const GLFWwindow, callback: GLFWdropfun) {
+    unsafe {
+        _glfwSetDropCallback(window, callback);
+    }
+}
+
 pub fn glfw_set_window_size_callback(window: *const GLFWwindow, callback: GLFWwindowsizefun){
     unsafe{
         _glfwSetWindowSizeCallback(window, callback);
@@ -136,10 +262,29 @@ pub fn glfw_poll_events() {
     unsafe { _glfwPollEvents() }
 }
 
+pub fn glfw_wait_events() {
+    unsafe { _glfwWaitEvents() }
+}
+
+pub fn glfw_wait_events_timeout(timeout: f64) {
+    unsafe { _glfwWaitEventsTimeout(timeout) }
+}
+
+/// Wakes up a thread blocked in [`glfw_wait_events`]. Can be called from any thread.
+pub fn glfw_post_empty_event() {
+    unsafe { _glfwPostEmptyEvent() }
+}
+
 pub fn glfw_swap_buffers(window: *const GLFWwindow) {
     unsafe { _glfwSwapBuffers(window) }
 }
 
+/// Sets the swap interval of the current context: `0` disables vsync, `1` waits for one
+/// vertical refresh per buffer swap.
+pub fn glfw_swap_interval(interval: i32) {
+    unsafe { _glfwSwapInterval(interval) }
+}
+
 pub fn glfw_destroy_window(window: *const GLFWwindow) {
     unsafe { _glfwDestroyWindow(window) }
 }
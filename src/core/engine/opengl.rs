@@ -6,6 +6,7 @@ pub type GLsizeiptr = c_long;
 pub type GLchar = c_char;
 pub type GLint = c_int;
 pub type GLuint = c_uint;
+pub type GLbitfield = c_uint;
 pub type GLfloat = c_float;
 pub type GLvoid = c_void;
 
@@ -44,12 +45,37 @@ pub const GL_FRAGMENT_SHADER: u32 = 0x8B30;
 pub const GL_VERTEX_SHADER: u32 = 0x8B31;
 pub const GL_GEOMETRY_SHADER: u32 = 0x8DD9;
 pub const GL_COMPILE_STATUS: u32 = 0x8B81;
+pub const GL_LINK_STATUS: u32 = 0x8B82;
+pub const GL_INFO_LOG_LENGTH: u32 = 0x8B84;
+pub const GL_ACTIVE_UNIFORMS: u32 = 0x8B86;
+pub const GL_ACTIVE_UNIFORM_MAX_LENGTH: u32 = 0x8B87;
+pub const GL_ACTIVE_ATTRIBUTES: u32 = 0x8B89;
+pub const GL_ACTIVE_ATTRIBUTE_MAX_LENGTH: u32 = 0x8B8A;
+
+pub const GL_INT: u32 = 0x1404;
+pub const GL_FLOAT_VEC2: u32 = 0x8B50;
+pub const GL_FLOAT_VEC3: u32 = 0x8B51;
+pub const GL_FLOAT_VEC4: u32 = 0x8B52;
+pub const GL_INT_VEC2: u32 = 0x8B53;
+pub const GL_INT_VEC3: u32 = 0x8B54;
+pub const GL_INT_VEC4: u32 = 0x8B55;
+pub const GL_BOOL: u32 = 0x8B56;
+pub const GL_FLOAT_MAT2: u32 = 0x8B5A;
+pub const GL_FLOAT_MAT3: u32 = 0x8B5B;
+pub const GL_FLOAT_MAT4: u32 = 0x8B5C;
+pub const GL_SAMPLER_2D: u32 = 0x8B5E;
+pub const GL_UNSIGNED_INT_VEC2: u32 = 0x8DC6;
+pub const GL_UNSIGNED_INT_VEC3: u32 = 0x8DC7;
+pub const GL_UNSIGNED_INT_VEC4: u32 = 0x8DC8;
 
 pub const GL_STATIC_DRAW: u32 = 0x88E4;
 pub const GL_DYNAMIC_DRAW: u32 = 0x88E8;
 pub const GL_FLOAT: u32 = 0x1406;
 pub const GL_UNSIGNED_INT: u32 = 0x1405;
+pub const GL_UNSIGNED_SHORT: u32 = 0x1403;
 pub const GL_UNSIGNED_BYTE: u32 = 0x1401;
+pub const GL_BYTE: u32 = 0x1400;
+pub const GL_SHORT: u32 = 0x1402;
 pub const GL_POINTS: u32 = 0x0000;
 pub const GL_LINES: u32 = 0x0001;
 pub const GL_LINE_STRIP: u32 = 0x0003;
@@ -61,10 +87,32 @@ pub const GL_TEXTURE_2D: u32 = 0x0DE1;
 pub const GL_RED: u32 = 0x1903;
 pub const GL_CULL_FACE: u32 = 0x0B44;
 pub const GL_BLEND: u32 = 0x0BE2;
+pub const GL_ZERO: u32 = 0;
+pub const GL_ONE: u32 = 1;
+pub const GL_SRC_COLOR: u32 = 0x0300;
+pub const GL_ONE_MINUS_SRC_COLOR: u32 = 0x0301;
 pub const GL_SRC_ALPHA: u32 = 0x0302;
 pub const GL_ONE_MINUS_SRC_ALPHA: u32 = 0x0303;
+pub const GL_DST_COLOR: u32 = 0x0306;
+pub const GL_FUNC_ADD: u32 = 0x8006;
 pub const GL_TEXTURE0: u32 = 0x84C0;
 
+pub const GL_COLOR_BUFFER_BIT: GLbitfield = 0x4000;
+pub const GL_DEPTH_BUFFER_BIT: GLbitfield = 0x0100;
+pub const GL_STENCIL_BUFFER_BIT: GLbitfield = 0x0400;
+
+pub const GL_FRAMEBUFFER: u32 = 0x8D40;
+pub const GL_READ_FRAMEBUFFER: u32 = 0x8CA8;
+pub const GL_DRAW_FRAMEBUFFER: u32 = 0x8CA9;
+pub const GL_DRAW_FRAMEBUFFER_BINDING: u32 = 0x8CA6;
+pub const GL_READ_FRAMEBUFFER_BINDING: u32 = 0x8CAA;
+pub const GL_FRAMEBUFFER_COMPLETE: u32 = 0x8CD5;
+pub const GL_RENDERBUFFER: u32 = 0x8D41;
+pub const GL_COLOR_ATTACHMENT0: u32 = 0x8CE0;
+pub const GL_DEPTH_STENCIL_ATTACHMENT: u32 = 0x821A;
+pub const GL_DEPTH24_STENCIL8: u32 = 0x88F0;
+pub const GL_MAX_SAMPLES: u32 = 0x8D57;
+
 pub const GL_TEXTURE_WRAP_S: u32 = 0x2802;
 pub const GL_TEXTURE_WRAP_T: u32 = 0x2803;
 pub const GL_REPEAT: GLint = 0x2901;
@@ -72,23 +120,59 @@ pub const GL_TEXTURE_MAG_FILTER: u32 = 0x2800;
 pub const GL_TEXTURE_MIN_FILTER: u32 = 0x2801;
 pub const GL_LINEAR: GLint = 0x2601;
 pub const GL_LINEAR_MIPMAP_LINEAR: GLint = 0x2703;
+pub const GL_NEAREST: GLint = 0x2600;
+pub const GL_NEAREST_MIPMAP_LINEAR: GLint = 0x2702;
+pub const GL_CLAMP_TO_EDGE: GLint = 0x812F;
+pub const GL_MIRRORED_REPEAT: GLint = 0x8370;
 pub const GL_RGB: GLint = 0x1907;
 pub const GL_RGBA: GLint = 0x1908;
+pub const GL_RG: u32 = 0x8227;
+pub const GL_R8: GLint = 0x8229;
+pub const GL_RG8: GLint = 0x822B;
+pub const GL_RGBA8: GLint = 0x8058;
+pub const GL_R32F: GLint = 0x822E;
+pub const GL_RGBA16F: GLint = 0x881A;
+pub const GL_PACK_ALIGNMENT: GLenum = 0x0D05;
+pub const GL_UNPACK_ALIGNMENT: GLenum = 0x0CF5;
 pub const GL_MULTISAMPLE: GLuint = 0x809D;
 pub const GL_SAMPLES: GLuint = 0x80A9;
 
 unsafe extern "C" {
     fn _glClearColor(red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat);
+    fn _glClear(mask: GLbitfield);
     fn _glViewPort(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
     fn _glGetIntegerv(pname: GLenum, data: *mut GLvoid);
+    fn _glReadPixels(
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        dataType: GLenum,
+        data: *mut GLvoid,
+    );
+    fn _glPixelStorei(pname: GLenum, param: GLint);
     fn _glCreateShader(shaderType: GLenum) -> GLuint;
     fn _glShaderSource(shader: GLuint, source: *const c_char);
     fn _glCompileShader(shader: GLuint);
     fn _glDeleteShader(shader: GLuint);
     fn _glGetShaderiv(shader: GLuint, pname: GLenum, params: *mut GLint);
+    fn _glGetShaderInfoLog(
+        shader: GLuint,
+        max_length: GLsizei,
+        length: *mut GLsizei,
+        info_log: *mut GLchar,
+    );
     fn _glCreateProgram() -> GLuint;
     fn _glAttachShader(program: GLuint, shader: GLuint);
     fn _glLinkProgram(program: GLuint);
+    fn _glGetProgramiv(program: GLuint, pname: GLenum, params: *mut GLint);
+    fn _glGetProgramInfoLog(
+        program: GLuint,
+        max_length: GLsizei,
+        length: *mut GLsizei,
+        info_log: *mut GLchar,
+    );
     fn _glDeleteProgram(program: GLuint);
     fn _glUseProgram(program: GLuint);
     fn _glGenBuffer() -> GLuint;
@@ -108,11 +192,69 @@ unsafe extern "C" {
         stride: GLsizei,
         offset: GLsizei,
     );
+    fn _glVertexAttribIPointer(
+        index: GLuint,
+        size: GLint,
+        dataType: GLenum,
+        stride: GLsizei,
+        offset: GLsizei,
+    );
     fn _glActiveTexture(unit: GLenum);
     fn _glGenTexture() -> GLuint;
     fn _glBindTexture(target: GLenum, texture: GLuint);
     fn _glTexParameteri(target: GLenum, pname: GLenum, param: GLint);
     fn _glGenerateMipmap(target: GLenum);
+    fn _glTexSubImage2D(
+        target: GLenum,
+        level: GLint,
+        xoffset: GLint,
+        yoffset: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        dataType: GLenum,
+        data: *const GLvoid,
+    );
+    fn _glDeleteTexture(texture: GLuint);
+    fn _glGenFramebuffer() -> GLuint;
+    fn _glDeleteFramebuffer(framebuffer: GLuint);
+    fn _glBindFramebuffer(target: GLenum, framebuffer: GLuint);
+    fn _glFramebufferTexture2D(
+        target: GLenum,
+        attachment: GLenum,
+        textarget: GLenum,
+        texture: GLuint,
+        level: GLint,
+    );
+    fn _glCheckFramebufferStatus(target: GLenum) -> GLenum;
+    fn _glGenRenderbuffer() -> GLuint;
+    fn _glDeleteRenderbuffer(renderbuffer: GLuint);
+    fn _glBindRenderbuffer(target: GLenum, renderbuffer: GLuint);
+    fn _glRenderbufferStorageMultisample(
+        target: GLenum,
+        samples: GLsizei,
+        internalformat: GLenum,
+        width: GLsizei,
+        height: GLsizei,
+    );
+    fn _glFramebufferRenderbuffer(
+        target: GLenum,
+        attachment: GLenum,
+        renderbuffertarget: GLenum,
+        renderbuffer: GLuint,
+    );
+    fn _glBlitFramebuffer(
+        src_x0: GLint,
+        src_y0: GLint,
+        src_x1: GLint,
+        src_y1: GLint,
+        dst_x0: GLint,
+        dst_y0: GLint,
+        dst_x1: GLint,
+        dst_y1: GLint,
+        mask: GLbitfield,
+        filter: GLenum,
+    );
     fn _glTexImage2D(
         target: GLenum,
         level: GLint,
@@ -125,16 +267,50 @@ unsafe extern "C" {
         data: *const GLvoid,
     );
     fn _glEnableVertexAttribArray(index: GLuint);
+    fn _glDisableVertexAttribArray(index: GLuint);
     fn _glDrawArrays(mode: GLenum, first: GLint, count: GLsizei);
     fn _glDrawArraysInstanced(mode: GLenum, first: GLint, count: GLsizei, instancecount: GLsizei);
     fn _glVertexAttribDivisor(index: GLuint, divisor: GLuint);
     fn _glDrawElements(mode: GLenum, count: GLsizei, element_type: GLenum, offset: GLuint);
+    fn _glDrawElementsInstanced(
+        mode: GLenum,
+        count: GLsizei,
+        element_type: GLenum,
+        offset: GLuint,
+        instancecount: GLsizei,
+    );
 
     fn _glGetUniformLocation(program: GLuint, name: *const GLchar) -> GLint;
+    fn _glGetActiveAttrib(
+        program: GLuint,
+        index: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        size: *mut GLint,
+        attrib_type: *mut GLenum,
+        name: *mut GLchar,
+    );
+    fn _glGetAttribLocation(program: GLuint, name: *const GLchar) -> GLint;
+    fn _glGetActiveUniform(
+        program: GLuint,
+        index: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        size: *mut GLint,
+        uniform_type: *mut GLenum,
+        name: *mut GLchar,
+    );
+    fn _glUniform1i(location: GLint, v0: GLint);
     fn _glUniform1f(location: GLint, v0: GLfloat);
     fn _glUniform2f(location: GLint, v0: GLfloat, v1: GLfloat);
     fn _glUniform3f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat);
     fn _glUniform4f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat, v3: GLfloat);
+    fn _glUniformMatrix3fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
     fn _glUniformMatrix4fv(
         location: GLint,
         count: GLsizei,
@@ -143,13 +319,20 @@ unsafe extern "C" {
     );
     fn _glPointSize(size: GLfloat);
     fn _glEnable(cap: GLenum);
+    fn _glDisable(cap: GLenum);
     fn _glBlendFunc(sfactor: GLenum, dfactor: GLenum);
+    fn _glBlendFuncSeparate(src_rgb: GLenum, dst_rgb: GLenum, src_alpha: GLenum, dst_alpha: GLenum);
+    fn _glBlendEquation(mode: GLenum);
 }
 
 pub fn gl_clear_color(red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat) {
     unsafe { _glClearColor(red, green, blue, alpha) }
 }
 
+pub fn gl_clear(mask: GLbitfield) {
+    unsafe { _glClear(mask) }
+}
+
 pub fn gl_viewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
     unsafe {
         _glViewPort(x, y, width, height);
@@ -162,6 +345,26 @@ pub fn gl_get_integerv(pname: GLenum, data: *mut GLvoid) {
     }
 }
 
+pub fn gl_read_pixels(
+    x: GLint,
+    y: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    data_type: GLenum,
+    data: *mut GLvoid,
+) {
+    unsafe {
+        _glReadPixels(x, y, width, height, format, data_type, data);
+    }
+}
+
+pub fn gl_pixel_storei(pname: GLenum, param: GLint) {
+    unsafe {
+        _glPixelStorei(pname, param);
+    }
+}
+
 pub fn gl_create_fragment_shader() -> GLuint {
     unsafe { _glCreateShader(GL_FRAGMENT_SHADER) }
 }
@@ -191,6 +394,33 @@ pub fn gl_get_shaderiv(shader: GLuint, pname: GLenum, params: &mut GLint) {
     unsafe { _glGetShaderiv(shader, pname, params as *mut GLint) }
 }
 
+/// Returns whether the last compilation of `shader` succeeded.
+pub fn gl_get_shader_compile_status(shader: GLuint) -> bool {
+    let mut status = 0;
+    gl_get_shaderiv(shader, GL_COMPILE_STATUS, &mut status);
+    status != 0
+}
+
+pub fn gl_get_shader_info_log(shader: GLuint) -> String {
+    let mut length = 0;
+    gl_get_shaderiv(shader, GL_INFO_LOG_LENGTH, &mut length);
+    if length <= 0 {
+        return String::new();
+    }
+    let mut buf = vec![0u8; length as usize];
+    let mut written: GLsizei = 0;
+    unsafe {
+        _glGetShaderInfoLog(
+            shader,
+            length,
+            &mut written,
+            buf.as_mut_ptr() as *mut GLchar,
+        );
+    }
+    buf.truncate(written.max(0) as usize);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
 pub fn gl_create_program() -> GLuint {
     unsafe { _glCreateProgram() }
 }
@@ -205,6 +435,37 @@ pub fn gl_link_program(program: GLuint) {
     }
 }
 
+pub fn gl_get_programiv(program: GLuint, pname: GLenum, params: &mut GLint) {
+    unsafe { _glGetProgramiv(program, pname, params as *mut GLint) }
+}
+
+/// Returns whether the last link of `program` succeeded.
+pub fn gl_get_program_link_status(program: GLuint) -> bool {
+    let mut status = 0;
+    gl_get_programiv(program, GL_LINK_STATUS, &mut status);
+    status != 0
+}
+
+pub fn gl_get_program_info_log(program: GLuint) -> String {
+    let mut length = 0;
+    gl_get_programiv(program, GL_INFO_LOG_LENGTH, &mut length);
+    if length <= 0 {
+        return String::new();
+    }
+    let mut buf = vec![0u8; length as usize];
+    let mut written: GLsizei = 0;
+    unsafe {
+        _glGetProgramInfoLog(
+            program,
+            length,
+            &mut written,
+            buf.as_mut_ptr() as *mut GLchar,
+        );
+    }
+    buf.truncate(written.max(0) as usize);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
 pub fn gl_delete_program(program: GLuint) {
     unsafe { _glDeleteProgram(program) }
 }
@@ -315,6 +576,12 @@ pub fn gl_enable_vertex_attrib_array(index: GLuint) {
     }
 }
 
+pub fn gl_disable_vertex_attrib_array(index: GLuint) {
+    unsafe {
+        _glDisableVertexAttribArray(index);
+    }
+}
+
 pub fn gl_vertex_attrib_pointer_float(
     index: GLuint,
     size: GLint,
@@ -327,6 +594,33 @@ pub fn gl_vertex_attrib_pointer_float(
     }
 }
 
+pub fn gl_vertex_attrib_pointer(
+    index: GLuint,
+    size: GLint,
+    data_type: GLenum,
+    normalize: GLboolean,
+    stride: GLsizei,
+    offset: GLsizei,
+) {
+    unsafe {
+        _glVertexAttribPointer(index, size, data_type, normalize, stride, offset);
+    }
+}
+
+/// Like [gl_vertex_attrib_pointer] for integer attributes, which the shader reads without
+/// conversion to float.
+pub fn gl_vertex_attrib_i_pointer(
+    index: GLuint,
+    size: GLint,
+    data_type: GLenum,
+    stride: GLsizei,
+    offset: GLsizei,
+) {
+    unsafe {
+        _glVertexAttribIPointer(index, size, data_type, stride, offset);
+    }
+}
+
 pub fn gl_draw_arrays(mode: GLenum, first: GLint, count: GLsizei) {
     unsafe {
         _glDrawArrays(mode, first, count);
@@ -354,6 +648,16 @@ pub fn gl_draw_elements(mode: GLenum, count: GLsizei, element_type: GLenum, offs
     unsafe { _glDrawElements(mode, count, element_type, offset) }
 }
 
+pub fn gl_draw_elements_instanced(
+    mode: GLenum,
+    count: GLsizei,
+    element_type: GLenum,
+    offset: GLuint,
+    instance_count: GLsizei,
+) {
+    unsafe { _glDrawElementsInstanced(mode, count, element_type, offset, instance_count) }
+}
+
 pub fn gl_get_uniform_location(program: GLuint, name: &str) -> GLint {
     const MAX_STACK_LEN: usize = 63;
 
@@ -371,6 +675,63 @@ pub fn gl_get_uniform_location(program: GLuint, name: &str) -> GLint {
     }
 }
 
+/// Returns the name, array size and type of the active attribute at `index` in `program`.
+pub fn gl_get_active_attrib(program: GLuint, index: GLuint) -> (String, GLint, GLenum) {
+    let mut max_length = 0;
+    gl_get_programiv(program, GL_ACTIVE_ATTRIBUTE_MAX_LENGTH, &mut max_length);
+    let mut buf = vec![0u8; max_length.max(1) as usize];
+    let mut length: GLsizei = 0;
+    let mut size: GLint = 0;
+    let mut attrib_type: GLenum = 0;
+    unsafe {
+        _glGetActiveAttrib(
+            program,
+            index,
+            buf.len() as GLsizei,
+            &mut length,
+            &mut size,
+            &mut attrib_type,
+            buf.as_mut_ptr() as *mut GLchar,
+        );
+    }
+    buf.truncate(length.max(0) as usize);
+    (String::from_utf8_lossy(&buf).into_owned(), size, attrib_type)
+}
+
+pub fn gl_get_attrib_location(program: GLuint, name: &str) -> GLint {
+    let c_string = CString::new(name).expect("CString::new failed");
+    unsafe { _glGetAttribLocation(program, c_string.as_ptr()) }
+}
+
+/// Returns the name, array size and type of the active uniform at `index` in `program`.
+pub fn gl_get_active_uniform(program: GLuint, index: GLuint) -> (String, GLint, GLenum) {
+    let mut max_length = 0;
+    gl_get_programiv(program, GL_ACTIVE_UNIFORM_MAX_LENGTH, &mut max_length);
+    let mut buf = vec![0u8; max_length.max(1) as usize];
+    let mut length: GLsizei = 0;
+    let mut size: GLint = 0;
+    let mut uniform_type: GLenum = 0;
+    unsafe {
+        _glGetActiveUniform(
+            program,
+            index,
+            buf.len() as GLsizei,
+            &mut length,
+            &mut size,
+            &mut uniform_type,
+            buf.as_mut_ptr() as *mut GLchar,
+        );
+    }
+    buf.truncate(length.max(0) as usize);
+    (String::from_utf8_lossy(&buf).into_owned(), size, uniform_type)
+}
+
+pub fn gl_uniform_1i(location: GLint, v0: GLint) {
+    unsafe {
+        _glUniform1i(location, v0);
+    }
+}
+
 pub fn gl_uniform_1f(location: GLint, v0: GLfloat) {
     unsafe {
         _glUniform1f(location, v0);
@@ -395,6 +756,17 @@ pub fn gl_uniform_4f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat, v3:
     }
 }
 
+pub fn gl_uniform_matrix_3fv(
+    location: GLint,
+    count: GLsizei,
+    transpose: GLboolean,
+    value: *const GLfloat,
+) {
+    unsafe {
+        _glUniformMatrix3fv(location, count, transpose, value);
+    }
+}
+
 pub fn gl_uniform_matrix_4fv(
     location: GLint,
     count: GLsizei,
@@ -416,10 +788,22 @@ pub fn gl_enable(cap: u32) {
     }
 }
 
+pub fn gl_disable(cap: u32) {
+    unsafe { _glDisable(cap) }
+}
+
 pub fn gl_blend_func(sfactor: GLenum, dfactor: GLenum) {
     unsafe { _glBlendFunc(sfactor, dfactor) }
 }
 
+pub fn gl_blend_func_separate(src_rgb: GLenum, dst_rgb: GLenum, src_alpha: GLenum, dst_alpha: GLenum) {
+    unsafe { _glBlendFuncSeparate(src_rgb, dst_rgb, src_alpha, dst_alpha) }
+}
+
+pub fn gl_blend_equation(mode: GLenum) {
+    unsafe { _glBlendEquation(mode) }
+}
+
 pub fn gl_active_texture(unit: GLenum) {
     unsafe {
         _glActiveTexture(unit);
@@ -438,6 +822,99 @@ pub fn gl_generate_mipmap(target: GLenum) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn gl_tex_sub_image_2d(
+    target: GLenum,
+    level: GLint,
+    xoffset: GLint,
+    yoffset: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    data_type: GLenum,
+    data: *const GLvoid,
+) {
+    unsafe {
+        _glTexSubImage2D(
+            target, level, xoffset, yoffset, width, height, format, data_type, data,
+        );
+    }
+}
+
+pub fn gl_delete_texture(texture: GLuint) {
+    unsafe {
+        _glDeleteTexture(texture);
+    }
+}
+
+pub fn gl_gen_framebuffer() -> GLuint {
+    unsafe { _glGenFramebuffer() }
+}
+
+pub fn gl_delete_framebuffer(framebuffer: GLuint) {
+    unsafe { _glDeleteFramebuffer(framebuffer) }
+}
+
+pub fn gl_bind_framebuffer(target: GLenum, framebuffer: GLuint) {
+    unsafe { _glBindFramebuffer(target, framebuffer) }
+}
+
+pub fn gl_framebuffer_texture_2d(
+    target: GLenum,
+    attachment: GLenum,
+    textarget: GLenum,
+    texture: GLuint,
+    level: GLint,
+) {
+    unsafe { _glFramebufferTexture2D(target, attachment, textarget, texture, level) }
+}
+
+pub fn gl_check_framebuffer_status(target: GLenum) -> GLenum {
+    unsafe { _glCheckFramebufferStatus(target) }
+}
+
+pub fn gl_gen_renderbuffer() -> GLuint {
+    unsafe { _glGenRenderbuffer() }
+}
+
+pub fn gl_delete_renderbuffer(renderbuffer: GLuint) {
+    unsafe { _glDeleteRenderbuffer(renderbuffer) }
+}
+
+pub fn gl_bind_renderbuffer(target: GLenum, renderbuffer: GLuint) {
+    unsafe { _glBindRenderbuffer(target, renderbuffer) }
+}
+
+/// Allocates the storage of the bound renderbuffer, multisampled unless `samples` is 0.
+pub fn gl_renderbuffer_storage_multisample(
+    target: GLenum,
+    samples: GLsizei,
+    internalformat: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+) {
+    unsafe { _glRenderbufferStorageMultisample(target, samples, internalformat, width, height) }
+}
+
+pub fn gl_framebuffer_renderbuffer(
+    target: GLenum,
+    attachment: GLenum,
+    renderbuffertarget: GLenum,
+    renderbuffer: GLuint,
+) {
+    unsafe { _glFramebufferRenderbuffer(target, attachment, renderbuffertarget, renderbuffer) }
+}
+
+/// Copies the `src` rectangle of the read framebuffer to the `dst` rectangle of the draw
+/// framebuffer, given as `[x0, y0, x1, y1]`.
+pub fn gl_blit_framebuffer(src: [GLint; 4], dst: [GLint; 4], mask: GLbitfield, filter: GLenum) {
+    unsafe {
+        _glBlitFramebuffer(
+            src[0], src[1], src[2], src[3], dst[0], dst[1], dst[2], dst[3], mask, filter,
+        )
+    }
+}
+
 pub fn gl_tex_image_2d(
     target: GLenum,
     level: GLint,
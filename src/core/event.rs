@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use crate::core::input::{Action, DragEvent, Key, Modifiers, MouseButton};
+
+/// A window or input event, as returned by [`crate::core::Window::drain_events`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// The window was resized, in screen coordinates.
+    Resize { width: i32, height: i32 },
+    Scroll { x_offset: f64, y_offset: f64 },
+    /// The cursor moved, in window coordinates.
+    CursorMoved { x: f64, y: f64 },
+    MouseButton { button: MouseButton, action: Action, modifiers: Modifiers },
+    Drag(DragEvent),
+    Key { key: Key, scancode: i32, action: Action, modifiers: Modifiers },
+    /// A Unicode character was typed.
+    Char(char),
+    /// The window gained (`true`) or lost (`false`) input focus.
+    Focus(bool),
+    /// The user attempted to close the window.
+    CloseRequested,
+    /// Files were dropped onto the window.
+    FileDrop(Vec<PathBuf>),
+}
@@ -0,0 +1,58 @@
+use std::sync::mpsc::{self, Receiver, SendError, Sender};
+
+use crate::core::app::WakeHandle;
+
+/// Sending half of a [`DataFeed`], created by [`crate::core::App::data_feed`].
+///
+/// Can be cloned and moved to any thread. Each message sent wakes the render loop, so
+/// [`crate::core::RedrawPolicy::OnDemand`] apps redraw as soon as new data arrives.
+pub struct DataSender<T> {
+    sender: Sender<T>,
+    wake: WakeHandle,
+}
+
+impl<T> Clone for DataSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            wake: self.wake.clone(),
+        }
+    }
+}
+
+impl<T> DataSender<T> {
+    /// Queues `message` for the render loop. Fails if the [`DataFeed`] has been dropped,
+    /// e.g. because the window was closed.
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        self.sender.send(message)?;
+        self.wake.request_redraw();
+        Ok(())
+    }
+}
+
+/// Receiving half of a data channel, drained on the render thread.
+///
+/// Attach it to an app with [`crate::core::App::attach_receiver`] to have pending messages
+/// handed to a callback once per frame.
+pub struct DataFeed<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T> DataFeed<T> {
+    /// Creates a channel whose sender wakes the loop behind `wake` on every message.
+    pub fn new(wake: WakeHandle) -> (DataSender<T>, DataFeed<T>) {
+        let (sender, receiver) = mpsc::channel();
+        (DataSender { sender, wake }, DataFeed { receiver })
+    }
+
+    /// Wraps an existing receiver. Its senders don't wake the render loop, so with
+    /// [`crate::core::RedrawPolicy::OnDemand`] messages are only picked up on the next frame.
+    pub fn from_receiver(receiver: Receiver<T>) -> Self {
+        Self { receiver }
+    }
+
+    /// Returns all pending messages, oldest first, without blocking.
+    pub fn drain(&self) -> Vec<T> {
+        self.receiver.try_iter().collect()
+    }
+}
@@ -1,10 +1,18 @@
-use crate::core::engine::opengl::{GL_ARRAY_BUFFER, GLboolean, GLenum, GLfloat, GLint, GLsizei, GLsizeiptr, GLuint, Vec2, gl_bind_buffer, gl_bind_vertex_array, gl_buffer_data, gl_buffer_data_empty, gl_buffer_sub_data_vec2, gl_delete_buffer, gl_delete_vertex_array, gl_enable_vertex_attrib_array, gl_gen_buffer, gl_gen_vertex_array, gl_vertex_attrib_divisor, gl_vertex_attrib_pointer_float};
+use crate::core::render_state;
+use crate::core::vertex::Vertex;
+use crate::core::engine::opengl::{GL_BYTE, GL_ELEMENT_ARRAY_BUFFER, GL_SHORT, GL_UNSIGNED_BYTE, GL_UNSIGNED_INT, GL_UNSIGNED_SHORT};
+use crate::core::engine::opengl::{GL_ARRAY_BUFFER, GL_FLOAT, GLboolean, GLenum, GLfloat, GLint, GLsizei, GLsizeiptr, GLuint, Vec2, gl_bind_buffer, gl_buffer_data, gl_buffer_data_empty, gl_buffer_sub_data, gl_delete_buffer, gl_delete_vertex_array, gl_disable_vertex_attrib_array, gl_enable_vertex_attrib_array, gl_gen_buffer, gl_gen_vertex_array, gl_vertex_attrib_divisor, gl_vertex_attrib_i_pointer, gl_vertex_attrib_pointer};
 
 #[derive(Debug, Clone)]
 pub struct Attribute {
     pub location: GLuint,
     pub size: GLint,
+    /// The component type in the buffer, e.g. `GL_FLOAT` or `GL_UNSIGNED_BYTE`.
+    pub data_type: GLenum,
     pub normalize: GLboolean,
+    /// Whether the shader reads the components as integers (`int`, `uvec2`, ...) rather
+    /// than converting them to float.
+    pub integer: bool,
     pub stride: GLsizei,
     offset: GLsizei,
     pub divisor: GLuint, // 0 = per-vertex, 1 = per-instance
@@ -16,28 +24,115 @@ impl Attribute {
         size: i32,
         stride_components: usize,
         offset_components: usize,
+    ) -> Self {
+        Self::from_bytes(
+            location,
+            size,
+            GL_FLOAT,
+            stride_components * std::mem::size_of::<GLfloat>(),
+            offset_components * std::mem::size_of::<GLfloat>(),
+        )
+    }
+
+    /// Creates an attribute of `size` components of `data_type`, with the stride and offset
+    /// given in bytes. This is the constructor to use for interleaved structs, typically
+    /// with `size_of::<T>()` and `offset_of!(T, field)`.
+    pub fn from_bytes(
+        location: u32,
+        size: i32,
+        data_type: GLenum,
+        stride_bytes: usize,
+        offset_bytes: usize,
     ) -> Self {
         Self {
             location,
             size,
+            data_type,
             normalize: GLboolean::FALSE,
-            stride: (stride_components * std::mem::size_of::<GLfloat>()) as GLsizei,
-            offset: (offset_components * std::mem::size_of::<GLfloat>()) as GLsizei,
+            integer: false,
+            stride: stride_bytes as GLsizei,
+            offset: offset_bytes as GLsizei,
             divisor: 0,
         }
     }
 
     pub fn instanced_vec2(location: u32) -> Self {
         // tightly packed vec2, divisor=1
-        Self {
-            location,
-            size: 2,
-            normalize: GLboolean::FALSE,
-            stride: (2 * std::mem::size_of::<GLfloat>()) as GLsizei,
-            offset: 0,
-            divisor: 1,
+        Self::new(location, 2, 2, 0).per_instance()
+    }
+
+    /// Advances the attribute once per instance instead of once per vertex.
+    pub fn per_instance(mut self) -> Self {
+        self.divisor = 1;
+        self
+    }
+
+    /// Passes the components to the shader as integers, for `int`/`uint` inputs.
+    pub fn integer(mut self) -> Self {
+        self.integer = true;
+        self
+    }
+
+    /// Maps integer components to `[0, 1]` (or `[-1, 1]` for signed types) when converting
+    /// them to float, e.g. for colors stored as `u8`. The shader then reads floats.
+    pub fn normalized(mut self) -> Self {
+        self.normalize = GLboolean::TRUE;
+        self.integer = false;
+        self
+    }
+
+    /// Bytes from one element to the next, computed from the components for tightly
+    /// packed attributes (a `stride` of 0).
+    fn byte_stride(&self) -> usize {
+        if self.stride != 0 {
+            return self.stride as usize;
         }
+        let component_size = match self.data_type {
+            GL_BYTE | GL_UNSIGNED_BYTE => 1,
+            GL_SHORT | GL_UNSIGNED_SHORT => 2,
+            _ => 4,
+        };
+        self.size as usize * component_size
     }
+
+    /// Points the currently bound vertex array's `location` at the buffer bound to
+    /// `GL_ARRAY_BUFFER`.
+    fn apply(&self) {
+        gl_enable_vertex_attrib_array(self.location);
+        if self.integer {
+            gl_vertex_attrib_i_pointer(
+                self.location,
+                self.size,
+                self.data_type,
+                self.stride,
+                self.offset,
+            );
+        } else {
+            gl_vertex_attrib_pointer(
+                self.location,
+                self.size,
+                self.data_type,
+                self.normalize,
+                self.stride,
+                self.offset,
+            );
+        }
+        gl_vertex_attrib_divisor(self.location, self.divisor);
+    }
+}
+
+/// An integer type usable for the indices of a [`Geometry`], see [`Geometry::set_indices`].
+pub trait VertexIndex: Copy {
+    /// The OpenGL type of the index, e.g. `GL_UNSIGNED_SHORT`.
+    const GL_TYPE: GLenum;
+}
+
+impl VertexIndex for u16 {
+    const GL_TYPE: GLenum = GL_UNSIGNED_SHORT;
+}
+
+impl VertexIndex for u32 {
+    const GL_TYPE: GLenum = GL_UNSIGNED_INT;
 }
 
 /// A GPU-backed buffer representing a drawable shape or mesh.
@@ -48,16 +143,28 @@ impl Attribute {
 pub struct Geometry {
     vao: GLuint,
     vbo: GLuint,
+    /// Size in bytes of the data store allocated for `vbo`.
+    vbo_capacity: usize,
+    /// Size in bytes of the vertex data currently in `vbo`.
+    vbo_len: usize,
     vertex_count: i32,
     drawing_mode: GLenum,
     attributes: Vec<Attribute>,
     // NEW
     instance_vbo: GLuint,
+    /// Size in bytes of the data store allocated for `instance_vbo`.
+    instance_capacity: usize,
     instance_count: i32,
+    ebo: GLuint,
+    index_count: i32,
+    index_type: GLenum,
 }
 
 impl Drop for Geometry {
     fn drop(&mut self) {
+        if self.ebo != 0 {
+            gl_delete_buffer(self.ebo);
+        }
         if self.instance_vbo != 0 {
             gl_delete_buffer(self.instance_vbo);
         }
@@ -65,6 +172,7 @@ impl Drop for Geometry {
             gl_delete_buffer(self.vbo);
         }
         if self.vao != 0 {
+            render_state::forget_vertex_array(self.vao);
             gl_delete_vertex_array(self.vao);
         }
     }
@@ -89,11 +197,17 @@ impl Geometry {
         Geometry {
             vao,
             vbo: 0,
+            vbo_capacity: 0,
+            vbo_len: 0,
             vertex_count: 0,
             attributes: Vec::new(),
             drawing_mode,
             instance_vbo: 0,
+            instance_capacity: 0,
             instance_count: 0,
+            ebo: 0,
+            index_count: 0,
+            index_type: GL_UNSIGNED_INT,
         }
     }
 
@@ -119,10 +233,108 @@ impl Geometry {
         self.vbo = gl_gen_buffer();
         self.vertex_count = buffer.len() as i32 / values_per_vertex;
 
-        gl_bind_vertex_array(self.vao);
+        render_state::bind_vertex_array(self.vao);
         gl_bind_buffer(GL_ARRAY_BUFFER, self.vbo);
         gl_buffer_data(GL_ARRAY_BUFFER, buffer);
-        gl_bind_vertex_array(0);
+        render_state::bind_vertex_array(0);
+
+        self.vbo_len = std::mem::size_of_val(buffer);
+        self.vbo_capacity = self.vbo_len;
+    }
+
+    /// Replaces the vertex data, reusing the existing VBO so that the attributes set up
+    /// with [Self::add_vertex_attribute] stay valid.
+    ///
+    /// The buffer grows (at least doubling) when `buffer` doesn't fit, otherwise its storage
+    /// is orphaned before uploading so that the driver doesn't stall on draws still using the
+    /// previous data. A VBO is created on first use if [Self::add_buffer] wasn't called.
+    pub fn update_buffer(&mut self, buffer: &[GLfloat], values_per_vertex: i32) {
+        self.upload_vertices(buffer, buffer.len() as i32 / values_per_vertex);
+    }
+
+    /// Creates a geometry holding `vertices`, with one attribute per field of `V`.
+    pub fn from_vertices<V: Vertex>(drawing_mode: GLenum, vertices: &[V]) -> Self {
+        let mut geometry = Geometry::new(drawing_mode);
+        geometry.upload_vertices(vertices, vertices.len() as i32);
+        for attribute in V::attributes() {
+            geometry.add_vertex_attribute(attribute);
+        }
+        geometry
+    }
+
+    /// Like [Self::update_buffer] for a geometry created with [Self::from_vertices].
+    pub fn update_vertices<V: Vertex>(&mut self, vertices: &[V]) {
+        self.upload_vertices(vertices, vertices.len() as i32);
+    }
+
+    fn upload_vertices<T: Copy>(&mut self, buffer: &[T], vertex_count: i32) {
+        let bytes = std::mem::size_of_val(buffer);
+        if self.vbo == 0 {
+            self.vbo = gl_gen_buffer();
+        }
+        if bytes > self.vbo_capacity {
+            self.vbo_capacity = bytes.max(self.vbo_capacity * 2);
+        }
+
+        gl_bind_buffer(GL_ARRAY_BUFFER, self.vbo);
+        // orphan: a fresh data store, the old one is released once pending draws are done
+        gl_buffer_data_empty(GL_ARRAY_BUFFER, self.vbo_capacity as GLsizeiptr);
+        gl_buffer_sub_data(GL_ARRAY_BUFFER, 0, buffer);
+        gl_bind_buffer(GL_ARRAY_BUFFER, 0);
+
+        self.vbo_len = bytes;
+        self.vertex_count = vertex_count;
+    }
+
+    /// Overwrites part of the vertex data, starting `offset` floats into the buffer.
+    ///
+    /// Unlike [Self::update_buffer] this doesn't orphan the buffer, so it is meant for small
+    /// changes such as moving the last point of a track. The range must lie within the data
+    /// uploaded so far.
+    pub fn update_buffer_range(&mut self, offset: usize, data: &[GLfloat]) {
+        let offset_bytes = offset * std::mem::size_of::<GLfloat>();
+        assert!(
+            offset_bytes + std::mem::size_of_val(data) <= self.vbo_len,
+            "update_buffer_range out of bounds"
+        );
+
+        gl_bind_buffer(GL_ARRAY_BUFFER, self.vbo);
+        gl_buffer_sub_data(GL_ARRAY_BUFFER, offset_bytes as GLsizeiptr, data);
+        gl_bind_buffer(GL_ARRAY_BUFFER, 0);
+    }
+
+    /// Uploads an index buffer, after which the geometry is drawn with `glDrawElements`
+    /// using `indices` to look up vertices, instead of drawing the vertices in order.
+    ///
+    /// Use `u16` indices when the geometry has at most 65536 vertices, `u32` otherwise.
+    /// Calling this again replaces the indices.
+    pub fn set_indices<I: VertexIndex>(&mut self, indices: &[I]) {
+        if self.ebo == 0 {
+            self.ebo = gl_gen_buffer();
+        }
+        // the element buffer binding is part of the VAO state, so it must stay bound
+        // until the VAO is unbound
+        render_state::bind_vertex_array(self.vao);
+        gl_bind_buffer(GL_ELEMENT_ARRAY_BUFFER, self.ebo);
+        gl_buffer_data(GL_ELEMENT_ARRAY_BUFFER, indices);
+        render_state::bind_vertex_array(0);
+
+        self.index_count = indices.len() as i32;
+        self.index_type = I::GL_TYPE;
+    }
+
+    /// Whether the geometry has an index buffer, see [`Geometry::set_indices`].
+    pub fn is_indexed(&self) -> bool {
+        self.ebo != 0
+    }
+
+    pub fn index_count(&self) -> i32 {
+        self.index_count
+    }
+
+    /// The OpenGL type of the indices, `GL_UNSIGNED_SHORT` or `GL_UNSIGNED_INT`.
+    pub fn index_type(&self) -> GLenum {
+        self.index_type
     }
 
     /// Defines a vertex attribute layout for this geometry object.
@@ -142,72 +354,97 @@ impl Geometry {
     /// # Notes
     ///
     /// - This must be called *after* [Self::add_buffer] has uploaded vertex data.
+    /// - The attribute reads from the vertex buffer, not the instance buffer; instance
+    ///   attributes are declared with [Self::enable_instancing].
     /// - The VAO is bound during the call and unbound afterward to preserve OpenGL state.
     /// - You can call this multiple times to add multiple attributes (e.g., position and color).
     pub fn add_vertex_attribute(&mut self, attribute: Attribute) {
-        gl_bind_vertex_array(self.vao);
-
-        gl_enable_vertex_attrib_array(attribute.location);
-        gl_vertex_attrib_pointer_float(
-            attribute.location,
-            attribute.size,
-            attribute.normalize,
-            attribute.stride,
-            attribute.offset,
-        );
+        render_state::bind_vertex_array(self.vao);
+        gl_bind_buffer(GL_ARRAY_BUFFER, self.vbo);
+        attribute.apply();
+        render_state::bind_vertex_array(0);
+        gl_bind_buffer(GL_ARRAY_BUFFER, 0);
+        self.set_attribute(attribute);
+    }
 
-        gl_vertex_attrib_divisor(attribute.location, attribute.divisor);
-        
-        gl_bind_vertex_array(0);
+    /// The attributes configured so far, at most one per location.
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Records `attribute`, replacing any previous one at the same location.
+    fn set_attribute(&mut self, attribute: Attribute) {
+        self.attributes.retain(|a| a.location != attribute.location);
         self.attributes.push(attribute);
     }
 
-    pub fn enable_instancing_xy(&mut self, max_instances: usize) {
+    /// Sets up a per-instance buffer laid out as described by `attributes`, with room for
+    /// `max_instances` instances. The buffer grows as needed in [Self::update_instances].
+    ///
+    /// The attributes usually describe the fields of one `#[repr(C)]` struct, see
+    /// [Attribute::from_bytes]; those without [Attribute::per_instance] are made per-instance.
+    pub fn enable_instancing(&mut self, attributes: &[Attribute], max_instances: usize) {
         if self.instance_vbo == 0 {
             self.instance_vbo = gl_gen_buffer();
         }
-        gl_bind_vertex_array(self.vao);
+        render_state::bind_vertex_array(self.vao);
         gl_bind_buffer(GL_ARRAY_BUFFER, self.instance_vbo);
 
-        // Allocate empty buffer of required capacity (you can also upload a zero slice)
-        let bytes = (max_instances * 2 * std::mem::size_of::<GLfloat>()) as GLsizei;
-        crate::core::engine::opengl::gl_buffer_data_empty(GL_ARRAY_BUFFER, bytes as GLsizeiptr); // add this tiny helper in FFI OR upload a zero slice
-
-        // Attribute at location=1, vec2, divisor=1
-        let inst_attr = Attribute::instanced_vec2(1);
-        gl_enable_vertex_attrib_array(inst_attr.location);
-        gl_vertex_attrib_pointer_float(
-            inst_attr.location,
-            inst_attr.size,
-            inst_attr.normalize,
-            inst_attr.stride,
-            inst_attr.offset,
-        );
-        gl_vertex_attrib_divisor(inst_attr.location, 1);
+        let stride = attributes.first().map_or(0, Attribute::byte_stride);
+        self.instance_capacity = max_instances * stride;
+        gl_buffer_data_empty(GL_ARRAY_BUFFER, self.instance_capacity as GLsizeiptr);
+
+        for attribute in attributes {
+            let mut attribute = attribute.clone();
+            attribute.divisor = attribute.divisor.max(1);
+            attribute.apply();
+            self.set_attribute(attribute);
+        }
 
-        gl_bind_vertex_array(0);
+        render_state::bind_vertex_array(0);
         gl_bind_buffer(GL_ARRAY_BUFFER, 0);
     }
 
-    pub fn update_instance_xy(&mut self, xy: &[Vec2]) {
-        if self.instance_vbo == 0 { return; }
-        gl_bind_vertex_array(self.vao);
-        gl_bind_buffer(GL_ARRAY_BUFFER, self.instance_vbo);
+    /// Uploads the per-instance data and sets the number of instances to draw.
+    ///
+    /// `T` must match the layout given to [Self::enable_instancing], i.e. be a
+    /// `#[repr(C)]` struct (or a plain array) of the declared components.
+    pub fn update_instances<T: Copy>(&mut self, instances: &[T]) {
+        if self.instance_vbo == 0 {
+            return;
+        }
+        let bytes = std::mem::size_of_val(instances);
+        self.instance_capacity = self.instance_capacity.max(bytes);
 
+        gl_bind_buffer(GL_ARRAY_BUFFER, self.instance_vbo);
         // orphan + upload
-        let bytes = (xy.len() * std::mem::size_of::<Vec2>()) as GLsizei;
-        gl_buffer_data_empty(GL_ARRAY_BUFFER, bytes as GLsizeiptr);
-        gl_buffer_sub_data_vec2(GL_ARRAY_BUFFER, xy);
-
-        gl_bind_vertex_array(0);
+        gl_buffer_data_empty(GL_ARRAY_BUFFER, self.instance_capacity as GLsizeiptr);
+        gl_buffer_sub_data(GL_ARRAY_BUFFER, 0, instances);
         gl_bind_buffer(GL_ARRAY_BUFFER, 0);
 
-        self.instance_count = xy.len() as i32;
+        self.instance_count = instances.len() as i32;
+    }
+
+    /// Sets up instancing with a single `vec2` translation per instance at location 1.
+    pub fn enable_instancing_xy(&mut self, max_instances: usize) {
+        self.enable_instancing(&[Attribute::instanced_vec2(1)], max_instances);
     }
 
+    pub fn update_instance_xy(&mut self, xy: &[Vec2]) {
+        self.update_instances(xy);
+    }
+
+    /// Stops drawing instances and disables the per-instance attributes, which
+    /// [Self::enable_instancing] sets up again.
     pub fn clear_instancing(&mut self) {
         self.instance_count = 0;
         // keep instance_vbo for reuse
+        render_state::bind_vertex_array(self.vao);
+        for attribute in self.attributes.iter().filter(|a| a.divisor > 0) {
+            gl_disable_vertex_attrib_array(attribute.location);
+        }
+        render_state::bind_vertex_array(0);
+        self.attributes.retain(|a| a.divisor == 0);
     }
 
     pub fn instance_count(&self) -> i32 { self.instance_count }
@@ -220,11 +457,15 @@ impl Geometry {
         self.vertex_count
     }
 
+    pub(crate) fn vao(&self) -> GLuint {
+        self.vao
+    }
+
     pub fn bind(&self) {
-        gl_bind_vertex_array(self.vao)
+        render_state::bind_vertex_array(self.vao)
     }
 
     pub fn unbind(&self) {
-        gl_bind_vertex_array(0)
+        render_state::bind_vertex_array(0)
     }
 }
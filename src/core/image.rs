@@ -1,4 +1,4 @@
-use image::{ImageReader};
+use image::{ExtendedColorType, ImageReader, ImageResult};
 
 
 // core/image.rs
@@ -8,6 +8,19 @@ pub struct Image {
     pub pixels: Vec<u8>, // RGBA8 format
 }
 
+impl Image {
+    /// Reverses the row order in place, converting between OpenGL's bottom-left origin
+    /// and the top-left origin used by image files.
+    pub fn flip_vertical(&mut self) {
+        let row_len = self.width as usize * 4;
+        let height = self.height as usize;
+        for y in 0..height / 2 {
+            let (top, bottom) = self.pixels.split_at_mut((height - 1 - y) * row_len);
+            top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
+        }
+    }
+}
+
 
 pub fn load_image(path: &str) -> Image {
     let img = ImageReader::open(path)
@@ -24,4 +37,15 @@ pub fn load_image(path: &str) -> Image {
         height,
         pixels,
     }
-}
\ No newline at end of file
+}
+
+/// Encodes `image` to `path`, the format is deduced from the file extension (e.g. `.png`).
+pub fn save_image(image: &Image, path: &str) -> ImageResult<()> {
+    image::save_buffer(
+        path,
+        &image.pixels,
+        image.width,
+        image.height,
+        ExtendedColorType::Rgba8,
+    )
+}
@@ -0,0 +1,465 @@
+use std::ops::BitOr;
+
+/// Keyboard keys, mirroring GLFW's key codes (`GLFW_KEY_*`).
+///
+/// Keys are named after their position on a US keyboard layout; use
+/// [`crate::core::Window::on_char`] for layout-aware text input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum Key {
+    Unknown = -1,
+    Space = 32,
+    Apostrophe = 39,
+    Comma = 44,
+    Minus = 45,
+    Period = 46,
+    Slash = 47,
+    Num0 = 48,
+    Num1 = 49,
+    Num2 = 50,
+    Num3 = 51,
+    Num4 = 52,
+    Num5 = 53,
+    Num6 = 54,
+    Num7 = 55,
+    Num8 = 56,
+    Num9 = 57,
+    Semicolon = 59,
+    Equal = 61,
+    A = 65,
+    B = 66,
+    C = 67,
+    D = 68,
+    E = 69,
+    F = 70,
+    G = 71,
+    H = 72,
+    I = 73,
+    J = 74,
+    K = 75,
+    L = 76,
+    M = 77,
+    N = 78,
+    O = 79,
+    P = 80,
+    Q = 81,
+    R = 82,
+    S = 83,
+    T = 84,
+    U = 85,
+    V = 86,
+    W = 87,
+    X = 88,
+    Y = 89,
+    Z = 90,
+    LeftBracket = 91,
+    Backslash = 92,
+    RightBracket = 93,
+    GraveAccent = 96,
+    /// Non-US key #1.
+    World1 = 161,
+    /// Non-US key #2.
+    World2 = 162,
+    Escape = 256,
+    Enter = 257,
+    Tab = 258,
+    Backspace = 259,
+    Insert = 260,
+    Delete = 261,
+    Right = 262,
+    Left = 263,
+    Down = 264,
+    Up = 265,
+    PageUp = 266,
+    PageDown = 267,
+    Home = 268,
+    End = 269,
+    CapsLock = 280,
+    ScrollLock = 281,
+    NumLock = 282,
+    PrintScreen = 283,
+    Pause = 284,
+    F1 = 290,
+    F2 = 291,
+    F3 = 292,
+    F4 = 293,
+    F5 = 294,
+    F6 = 295,
+    F7 = 296,
+    F8 = 297,
+    F9 = 298,
+    F10 = 299,
+    F11 = 300,
+    F12 = 301,
+    F13 = 302,
+    F14 = 303,
+    F15 = 304,
+    F16 = 305,
+    F17 = 306,
+    F18 = 307,
+    F19 = 308,
+    F20 = 309,
+    F21 = 310,
+    F22 = 311,
+    F23 = 312,
+    F24 = 313,
+    F25 = 314,
+    Kp0 = 320,
+    Kp1 = 321,
+    Kp2 = 322,
+    Kp3 = 323,
+    Kp4 = 324,
+    Kp5 = 325,
+    Kp6 = 326,
+    Kp7 = 327,
+    Kp8 = 328,
+    Kp9 = 329,
+    KpDecimal = 330,
+    KpDivide = 331,
+    KpMultiply = 332,
+    KpSubtract = 333,
+    KpAdd = 334,
+    KpEnter = 335,
+    KpEqual = 336,
+    LeftShift = 340,
+    LeftControl = 341,
+    LeftAlt = 342,
+    LeftSuper = 343,
+    RightShift = 344,
+    RightControl = 345,
+    RightAlt = 346,
+    RightSuper = 347,
+    Menu = 348,
+}
+
+impl Key {
+    /// Converts a raw GLFW key code into a [`Key`], returning [`Key::Unknown`] for
+    /// codes that have no mapping.
+    pub fn from_glfw(code: i32) -> Self {
+        match code {
+            32 => Key::Space,
+            39 => Key::Apostrophe,
+            44 => Key::Comma,
+            45 => Key::Minus,
+            46 => Key::Period,
+            47 => Key::Slash,
+            48 => Key::Num0,
+            49 => Key::Num1,
+            50 => Key::Num2,
+            51 => Key::Num3,
+            52 => Key::Num4,
+            53 => Key::Num5,
+            54 => Key::Num6,
+            55 => Key::Num7,
+            56 => Key::Num8,
+            57 => Key::Num9,
+            59 => Key::Semicolon,
+            61 => Key::Equal,
+            65 => Key::A,
+            66 => Key::B,
+            67 => Key::C,
+            68 => Key::D,
+            69 => Key::E,
+            70 => Key::F,
+            71 => Key::G,
+            72 => Key::H,
+            73 => Key::I,
+            74 => Key::J,
+            75 => Key::K,
+            76 => Key::L,
+            77 => Key::M,
+            78 => Key::N,
+            79 => Key::O,
+            80 => Key::P,
+            81 => Key::Q,
+            82 => Key::R,
+            83 => Key::S,
+            84 => Key::T,
+            85 => Key::U,
+            86 => Key::V,
+            87 => Key::W,
+            88 => Key::X,
+            89 => Key::Y,
+            90 => Key::Z,
+            91 => Key::LeftBracket,
+            92 => Key::Backslash,
+            93 => Key::RightBracket,
+            96 => Key::GraveAccent,
+            161 => Key::World1,
+            162 => Key::World2,
+            256 => Key::Escape,
+            257 => Key::Enter,
+            258 => Key::Tab,
+            259 => Key::Backspace,
+            260 => Key::Insert,
+            261 => Key::Delete,
+            262 => Key::Right,
+            263 => Key::Left,
+            264 => Key::Down,
+            265 => Key::Up,
+            266 => Key::PageUp,
+            267 => Key::PageDown,
+            268 => Key::Home,
+            269 => Key::End,
+            280 => Key::CapsLock,
+            281 => Key::ScrollLock,
+            282 => Key::NumLock,
+            283 => Key::PrintScreen,
+            284 => Key::Pause,
+            290 => Key::F1,
+            291 => Key::F2,
+            292 => Key::F3,
+            293 => Key::F4,
+            294 => Key::F5,
+            295 => Key::F6,
+            296 => Key::F7,
+            297 => Key::F8,
+            298 => Key::F9,
+            299 => Key::F10,
+            300 => Key::F11,
+            301 => Key::F12,
+            302 => Key::F13,
+            303 => Key::F14,
+            304 => Key::F15,
+            305 => Key::F16,
+            306 => Key::F17,
+            307 => Key::F18,
+            308 => Key::F19,
+            309 => Key::F20,
+            310 => Key::F21,
+            311 => Key::F22,
+            312 => Key::F23,
+            313 => Key::F24,
+            314 => Key::F25,
+            320 => Key::Kp0,
+            321 => Key::Kp1,
+            322 => Key::Kp2,
+            323 => Key::Kp3,
+            324 => Key::Kp4,
+            325 => Key::Kp5,
+            326 => Key::Kp6,
+            327 => Key::Kp7,
+            328 => Key::Kp8,
+            329 => Key::Kp9,
+            330 => Key::KpDecimal,
+            331 => Key::KpDivide,
+            332 => Key::KpMultiply,
+            333 => Key::KpSubtract,
+            334 => Key::KpAdd,
+            335 => Key::KpEnter,
+            336 => Key::KpEqual,
+            340 => Key::LeftShift,
+            341 => Key::LeftControl,
+            342 => Key::LeftAlt,
+            343 => Key::LeftSuper,
+            344 => Key::RightShift,
+            345 => Key::RightControl,
+            346 => Key::RightAlt,
+            347 => Key::RightSuper,
+            348 => Key::Menu,
+            _ => Key::Unknown,
+        }
+    }
+
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+/// State transition reported by key and mouse button callbacks (`GLFW_RELEASE`,
+/// `GLFW_PRESS`, `GLFW_REPEAT`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Release,
+    Press,
+    Repeat,
+}
+
+impl Action {
+    pub fn from_glfw(action: i32) -> Self {
+        match action {
+            0 => Action::Release,
+            2 => Action::Repeat,
+            _ => Action::Press,
+        }
+    }
+}
+
+/// Modifier key bit set, mirroring GLFW's `GLFW_MOD_*` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(i32);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const SHIFT: Modifiers = Modifiers(0x0001);
+    pub const CONTROL: Modifiers = Modifiers(0x0002);
+    pub const ALT: Modifiers = Modifiers(0x0004);
+    pub const SUPER: Modifiers = Modifiers(0x0008);
+    pub const CAPS_LOCK: Modifiers = Modifiers(0x0010);
+    pub const NUM_LOCK: Modifiers = Modifiers(0x0020);
+
+    pub fn from_glfw(mods: i32) -> Self {
+        Modifiers(mods)
+    }
+
+    pub fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// Returns `true` if all modifiers in `other` are set.
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn shift(self) -> bool {
+        self.contains(Modifiers::SHIFT)
+    }
+
+    pub fn control(self) -> bool {
+        self.contains(Modifiers::CONTROL)
+    }
+
+    pub fn alt(self) -> bool {
+        self.contains(Modifiers::ALT)
+    }
+
+    pub fn super_key(self) -> bool {
+        self.contains(Modifiers::SUPER)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+/// Mouse buttons, mirroring GLFW's `GLFW_MOUSE_BUTTON_*` codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Button4,
+    Button5,
+    Button6,
+    Button7,
+    Button8,
+}
+
+impl MouseButton {
+    pub fn from_glfw(button: i32) -> Option<Self> {
+        match button {
+            0 => Some(MouseButton::Left),
+            1 => Some(MouseButton::Right),
+            2 => Some(MouseButton::Middle),
+            3 => Some(MouseButton::Button4),
+            4 => Some(MouseButton::Button5),
+            5 => Some(MouseButton::Button6),
+            6 => Some(MouseButton::Button7),
+            7 => Some(MouseButton::Button8),
+            _ => None,
+        }
+    }
+}
+
+/// Drag gesture events, in window coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragEvent {
+    /// The cursor moved past the drag threshold with `button` held. `x`/`y` is the
+    /// position where the button was pressed.
+    Start { button: MouseButton, x: f64, y: f64 },
+    /// The cursor moved during a drag. `dx`/`dy` is the offset since the previous event.
+    Move { button: MouseButton, x: f64, y: f64, dx: f64, dy: f64 },
+    /// The button was released. `dx`/`dy` is the total offset since the drag started.
+    End { button: MouseButton, x: f64, y: f64, dx: f64, dy: f64 },
+}
+
+/// Turns raw mouse button and cursor events into [`DragEvent`]s.
+///
+/// A drag starts once the cursor has moved more than `threshold` pixels away from
+/// where a button was pressed, so that plain clicks don't produce drags. Only the
+/// first button pressed is tracked until it is released.
+#[derive(Debug, Clone)]
+pub struct DragTracker {
+    threshold: f64,
+    pressed: Option<(MouseButton, f64, f64)>,
+    dragging: bool,
+    last: (f64, f64),
+}
+
+impl Default for DragTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DragTracker {
+    pub const DEFAULT_THRESHOLD: f64 = 3.0;
+
+    pub fn new() -> Self {
+        Self::with_threshold(Self::DEFAULT_THRESHOLD)
+    }
+
+    pub fn with_threshold(threshold: f64) -> Self {
+        Self {
+            threshold,
+            pressed: None,
+            dragging: false,
+            last: (0.0, 0.0),
+        }
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Feeds a mouse button event at cursor position (`x`, `y`).
+    pub fn button<F>(&mut self, button: MouseButton, action: Action, x: f64, y: f64, mut emit: F)
+    where
+        F: FnMut(DragEvent),
+    {
+        match action {
+            Action::Press if self.pressed.is_none() => {
+                self.pressed = Some((button, x, y));
+                self.dragging = false;
+                self.last = (x, y);
+            }
+            Action::Release => {
+                if let Some((pressed_button, x0, y0)) = self.pressed {
+                    if pressed_button != button {
+                        return;
+                    }
+                    if self.dragging {
+                        emit(DragEvent::End { button, x, y, dx: x - x0, dy: y - y0 });
+                    }
+                    self.pressed = None;
+                    self.dragging = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Feeds a cursor movement to position (`x`, `y`).
+    pub fn cursor_moved<F>(&mut self, x: f64, y: f64, mut emit: F)
+    where
+        F: FnMut(DragEvent),
+    {
+        let Some((button, x0, y0)) = self.pressed else {
+            return;
+        };
+        if !self.dragging {
+            if (x - x0).hypot(y - y0) <= self.threshold {
+                return;
+            }
+            self.dragging = true;
+            emit(DragEvent::Start { button, x: x0, y: y0 });
+        }
+        let (last_x, last_y) = self.last;
+        self.last = (x, y);
+        emit(DragEvent::Move { button, x, y, dx: x - last_x, dy: y - last_y });
+    }
+}
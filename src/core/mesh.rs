@@ -2,9 +2,21 @@ use std::rc::Rc;
 use glam::Mat4;
 
 use crate::core::{geometry::Geometry, shader::Shader};
+use crate::core::blend::BlendMode;
 use crate::core::color::Color;
-use crate::core::engine::opengl::{gl_get_uniform_location, gl_uniform_4f};
-use crate::core::engine::opengl::GLuint;
+use crate::core::texture::Texture;
+use crate::core::uniform::UniformValue;
+
+/// Sampler uniform that [`Mesh::with_texture`] binds its texture to, as in the built-in
+/// image shader.
+const DEFAULT_SAMPLER: &str = "texture1";
+
+/// A texture bound to a sampler uniform of the mesh's shader.
+#[derive(Debug, Clone)]
+pub struct TextureBinding {
+    pub sampler: String,
+    pub texture: Rc<Texture>,
+}
 
 pub struct Mesh {
     pub geometry: Geometry,
@@ -12,7 +24,9 @@ pub struct Mesh {
     transform: Mat4,
     screen_offset: Option<(f32, f32)>,
     pub color: Option<Color>,
-    pub texture: Option<GLuint>,
+    blend_mode: BlendMode,
+    textures: Vec<TextureBinding>,
+    uniforms: Vec<(String, Box<dyn UniformValue>)>,
 }
 
 impl Mesh {
@@ -24,7 +38,9 @@ impl Mesh {
             transform: Mat4::IDENTITY,
             screen_offset: None,
             color: None,
-            texture: None
+            blend_mode: BlendMode::default(),
+            textures: Vec::new(),
+            uniforms: Vec::new(),
         }
     }
     
@@ -35,25 +51,80 @@ impl Mesh {
             transform: Mat4::IDENTITY,
             screen_offset: None,
             color,
-            texture: None
+            blend_mode: BlendMode::default(),
+            textures: Vec::new(),
+            uniforms: Vec::new(),
         }
     }
     
-    pub fn with_texture(shader: Rc<Shader>, geometry: Geometry, texture: Option<GLuint>)->Self{
-        Self {
-            geometry,
-            shader,
-            transform: Mat4::IDENTITY,
-            screen_offset: None,
-            color: None,
-            texture
+    /// Creates a mesh sampling `texture` through the `texture1` sampler, see
+    /// [`Mesh::set_texture`] for other samplers.
+    pub fn with_texture(shader: Rc<Shader>, geometry: Geometry, texture: Option<Rc<Texture>>)->Self{
+        let mut mesh = Self::new(shader, geometry);
+        if let Some(texture) = texture {
+            mesh.set_texture(DEFAULT_SAMPLER, texture);
         }
+        mesh
+    }
+
+    /// Binds `texture` to the sampler uniform `sampler`, replacing any texture previously
+    /// bound to it. The renderer assigns texture units in the order the samplers were
+    /// first bound.
+    pub fn set_texture(&mut self, sampler: &str, texture: Rc<Texture>) {
+        match self.textures.iter_mut().find(|b| b.sampler == sampler) {
+            Some(binding) => binding.texture = texture,
+            None => self.textures.push(TextureBinding {
+                sampler: sampler.to_string(),
+                texture,
+            }),
+        }
+    }
+
+    /// Unbinds the texture of `sampler`, returning it.
+    pub fn remove_texture(&mut self, sampler: &str) -> Option<Rc<Texture>> {
+        let index = self.textures.iter().position(|b| b.sampler == sampler)?;
+        Some(self.textures.remove(index).texture)
+    }
+
+    /// The texture bound to `sampler`.
+    pub fn texture(&self, sampler: &str) -> Option<&Rc<Texture>> {
+        self.textures
+            .iter()
+            .find(|b| b.sampler == sampler)
+            .map(|b| &b.texture)
+    }
+
+    pub fn textures(&self) -> &[TextureBinding] {
+        &self.textures
+    }
+
+    /// Sets how the mesh is blended with what is already drawn. The shader must output
+    /// premultiplied colors for the modes that [expect them](BlendMode::expects_premultiplied).
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Sets a uniform that the renderer uploads each time this mesh is drawn, replacing any
+    /// previous value for `name`.
+    pub fn set_uniform(&mut self, name: &str, value: impl UniformValue + 'static) {
+        match self.uniforms.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = Box::new(value),
+            None => self.uniforms.push((name.to_string(), Box::new(value))),
+        }
+    }
+
+    pub(crate) fn uniforms(&self) -> impl Iterator<Item = (&str, &dyn UniformValue)> {
+        self.uniforms.iter().map(|(n, v)| (n.as_str(), v.as_ref()))
     }
 
-    // needs to go into renderer!
+    #[deprecated(note = "use `Mesh::set_uniform`, which the renderer applies when drawing")]
     pub fn set_uniform_4f(&self, location: &str, vec4: &[f32; 4]) {
-        let loc = gl_get_uniform_location(self.shader.program(), location);
-        gl_uniform_4f(loc, vec4[0], vec4[1], vec4[2], vec4[3]);
+        self.shader.use_program();
+        self.shader.set_uniform(location, glam::Vec4::from_array(*vec4));
     }
 
     pub fn set_transform(&mut self, transform: Mat4) {
@@ -1,23 +1,56 @@
 mod geometry;
 mod mesh;
 mod renderer;
+mod blend;
+mod render_state;
 mod shader;
+mod reflection;
+mod preprocessor;
+mod shader_watcher;
+mod uniform;
+mod vertex;
 mod window;
 mod app;
 mod color;
 mod texture;
+mod render_target;
+mod post_process;
+mod persistence;
 mod image;
+mod input;
+mod event;
+mod feed;
 pub mod engine;
 
 pub use self::geometry::Attribute;
 pub use self::geometry::Geometry;
+pub use self::geometry::VertexIndex;
+pub use self::vertex::{Vertex, VertexComponent};
+#[doc(hidden)]
+pub use self::vertex::vertex_field_attribute;
 pub use self::engine::opengl::Vec2;
-pub use self::mesh::Mesh;
+pub use self::mesh::{Mesh, TextureBinding};
 pub use self::renderer::Renderer;
 pub use self::renderer::Renderable;
-pub use self::shader::Shader;
+pub use self::render_state::FrameStats;
+pub use self::blend::BlendMode;
+pub use self::shader::{Shader, ShaderError, ShaderSource, ShaderStage};
+pub use self::reflection::{ShaderVariable, ValidationIssue};
+pub use self::shader_watcher::ShaderWatcher;
+pub use self::preprocessor::{PreprocessError, ShaderPreprocessor, ShaderVariants};
+pub use self::uniform::UniformValue;
 pub use self::window::Window;
-pub use self::app::App;
+pub use self::window::WindowBuilder;
+pub use self::window::WindowHandle;
+pub use self::event::Event;
+pub use self::feed::{DataFeed, DataSender};
+pub use self::input::{Action, DragEvent, DragTracker, Key, Modifiers, MouseButton};
+pub use self::app::{App, FrameInfo, RedrawPolicy, WakeHandle};
 pub use self::color::Color;
+pub use self::render_target::{RenderTarget, RenderTargetBuilder};
+pub use self::post_process::{PostEffect, PostEffects, PostProcess};
+pub use self::persistence::Persistence;
+pub use self::texture::{Texture, TextureFilter, TextureFormat, TextureOptions, TextureWrap};
+#[allow(deprecated)]
 pub use texture::generate_texture_from_image;
-pub use image::load_image;
\ No newline at end of file
+pub use self::image::{Image, load_image, save_image};
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::core::blend::{BlendFunc, BlendMode};
+use crate::core::engine::opengl::{
+    GL_COLOR_BUFFER_BIT, GL_FRAMEBUFFER, gl_bind_framebuffer, gl_clear, gl_clear_color,
+};
+use crate::core::post_process::FullscreenQuad;
+use crate::core::render_state;
+use crate::core::render_target::{RenderTarget, bound_framebuffer};
+use crate::core::texture::{TextureFormat, TextureOptions, TextureWrap};
+
+/// Phosphor-like afterglow: what is drawn stays on screen and fades out over time,
+/// instead of being cleared every frame.
+///
+/// Drawing goes to a persistent offscreen buffer which is dimmed before each frame, then
+/// composited over the framebuffer it was going to. Attach it to an [`App`] with
+/// [`App::set_persistence`], or wrap drawing with [`Persistence::render`].
+///
+/// [`App`]: crate::core::App
+/// [`App::set_persistence`]: crate::core::App::set_persistence
+pub struct Persistence {
+    decay: f32,
+    samples: i32,
+    /// Accumulated image, of the viewport size. RGBA16F so that faint trails keep fading
+    /// instead of getting stuck at the smallest 8-bit value.
+    buffer: Option<RenderTarget>,
+    quad: FullscreenQuad,
+}
+
+impl Persistence {
+    /// Keeps `decay` (in `[0, 1]`) of the brightness of older content per second: `0.0`
+    /// clears each frame, `1.0` never fades.
+    pub fn new(decay: f32) -> Self {
+        let mut persistence = Self {
+            decay: 1.0,
+            samples: 0,
+            buffer: None,
+            quad: FullscreenQuad::new(),
+        };
+        persistence.set_decay(decay);
+        persistence
+    }
+
+    /// Fades content to half its brightness every `seconds`.
+    pub fn with_half_life(seconds: f32) -> Self {
+        assert!(seconds > 0.0, "Half-life must be positive");
+        Self::new(0.5f32.powf(1.0 / seconds))
+    }
+
+    /// Draws with `samples` samples per pixel, see [`RenderTargetBuilder::samples`].
+    ///
+    /// [`RenderTargetBuilder::samples`]: crate::core::RenderTargetBuilder::samples
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.samples = samples.max(0);
+        self.buffer = None;
+        self
+    }
+
+    pub fn set_decay(&mut self, decay: f32) {
+        assert!(
+            (0.0..=1.0).contains(&decay),
+            "Decay must be between 0 and 1, got {decay}"
+        );
+        self.decay = decay;
+    }
+
+    pub fn decay(&self) -> f32 {
+        self.decay
+    }
+
+    /// Seconds until content fades below half an 8-bit step, i.e. out of sight. 0 when
+    /// content is cleared each frame or never fades.
+    pub(crate) fn fade_duration(&self) -> f64 {
+        if self.decay <= 0.0 || self.decay >= 1.0 {
+            return 0.0;
+        }
+        (1.0f64 / 512.0).ln() / (self.decay as f64).ln()
+    }
+
+    /// Erases the accumulated content, e.g. after panning or zooming.
+    pub fn clear(&mut self) {
+        self.buffer = None;
+    }
+
+    /// Fades the accumulated content by `dt` seconds, runs `draw` on top of it, and
+    /// composites the result over the framebuffer bound beforehand, which the caller
+    /// clears.
+    pub fn render(&mut self, dt: f64, draw: impl FnOnce()) {
+        let (output_framebuffer, output_viewport) = bound_framebuffer();
+        let (width, height) = (output_viewport[2].max(1), output_viewport[3].max(1));
+
+        if self.buffer.as_ref().map(RenderTarget::size) != Some((width, height)) {
+            let buffer = RenderTarget::builder(width as u32, height as u32)
+                .samples(self.samples)
+                .texture_options(
+                    TextureOptions::default()
+                        .format(TextureFormat::RGBA16F)
+                        .wrap(TextureWrap::ClampToEdge)
+                        .mipmaps(false),
+                )
+                .build();
+            gl_bind_framebuffer(GL_FRAMEBUFFER, buffer.draw_framebuffer());
+            gl_clear_color(0.0, 0.0, 0.0, 0.0);
+            gl_clear(GL_COLOR_BUFFER_BIT);
+            self.buffer = Some(buffer);
+        }
+        let buffer = self.buffer.as_ref().unwrap();
+
+        gl_bind_framebuffer(GL_FRAMEBUFFER, buffer.draw_framebuffer());
+        render_state::set_viewport([0, 0, width, height]);
+
+        // multiply the accumulated colors by the decay over dt
+        let fade = self.decay.powf(dt.max(0.0) as f32);
+        render_state::set_blend(Some(BlendFunc::MODULATE));
+        self.quad.draw(&["FILL"], &[], |shader| {
+            shader.set_uniform("u_color", glam::Vec4::splat(fade));
+        });
+
+        draw();
+
+        buffer.resolve();
+        gl_bind_framebuffer(GL_FRAMEBUFFER, output_framebuffer);
+        let [x, y, output_width, output_height] = output_viewport;
+        render_state::set_viewport([x, y, output_width, output_height]);
+
+        // the buffer holds colors already weighted by their alpha
+        render_state::set_blend(Some(BlendMode::Premultiplied.func()));
+        self.quad.draw(&[], &[buffer.texture().as_ref()], |_| {});
+    }
+}
@@ -0,0 +1,357 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glam::Vec2;
+
+use crate::core::color::Color;
+use crate::core::engine::opengl::{
+    GL_COLOR_BUFFER_BIT, GL_DEPTH_BUFFER_BIT, GL_FRAMEBUFFER, GL_STENCIL_BUFFER_BIT,
+    GL_TRIANGLE_STRIP, GLint, GLuint, gl_bind_framebuffer, gl_clear, gl_clear_color,
+    gl_draw_arrays,
+};
+use crate::core::geometry::{Attribute, Geometry};
+use crate::core::preprocessor::ShaderVariants;
+use crate::core::render_state;
+use crate::core::render_target::{RenderTarget, bound_framebuffer};
+use crate::core::shader::Shader;
+use crate::core::texture::Texture;
+
+thread_local! {
+    static POST_SHADERS: ShaderVariants = ShaderVariants::new(
+        include_str!("shaders/post.vert"),
+        include_str!("shaders/post.frag"),
+        None,
+    );
+}
+
+/// A full-screen effect applied by a [`PostProcess`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostEffect {
+    /// Gaussian blur with a standard deviation of `radius` pixels, capped at about 10.7
+    /// pixels as the kernel samples at most 32 pixels on each side.
+    Blur { radius: f32 },
+    /// Makes everything brighter than `threshold` (in `[0, 1]`) glow, adding its colors
+    /// blurred by `radius` pixels and scaled by `intensity` on top of the image.
+    ///
+    /// To highlight selected objects, draw them in colors above the threshold and keep
+    /// the rest of the scene below it.
+    Bloom {
+        threshold: f32,
+        intensity: f32,
+        radius: f32,
+    },
+    /// Adds `brightness`, scales the contrast around mid-gray by `contrast` and mixes
+    /// between grayscale (`saturation = 0`) and the original colors (`1`).
+    ColorGrade {
+        brightness: f32,
+        contrast: f32,
+        saturation: f32,
+    },
+    /// Darkens by up to `strength` the pixels further than `radius` from the center, in
+    /// texture coordinates, fading in over `softness`.
+    Vignette {
+        strength: f32,
+        radius: f32,
+        softness: f32,
+    },
+}
+
+impl PostEffect {
+    /// A bloom suited to highlighting saturated colors over a dark scene.
+    pub fn glow() -> Self {
+        PostEffect::Bloom {
+            threshold: 0.7,
+            intensity: 1.5,
+            radius: 8.0,
+        }
+    }
+
+    /// Color grading that leaves the image unchanged, to adjust from.
+    pub fn color_grade() -> Self {
+        PostEffect::ColorGrade {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+        }
+    }
+
+    pub fn vignette(strength: f32) -> Self {
+        PostEffect::Vignette {
+            strength,
+            radius: 0.4,
+            softness: 0.35,
+        }
+    }
+}
+
+/// Shared handle to the effects of a [`PostProcess`], to change them while it is owned
+/// by an [`App`](crate::core::App). Changes apply from the next frame.
+#[derive(Debug, Clone, Default)]
+pub struct PostEffects {
+    effects: Rc<RefCell<Vec<PostEffect>>>,
+}
+
+impl PostEffects {
+    pub fn get(&self) -> Vec<PostEffect> {
+        self.effects.borrow().clone()
+    }
+
+    pub fn set(&self, effects: Vec<PostEffect>) {
+        *self.effects.borrow_mut() = effects;
+    }
+
+    /// Edits the effects in place, e.g. to toggle or tune one.
+    pub fn update(&self, edit: impl FnOnce(&mut Vec<PostEffect>)) {
+        edit(&mut self.effects.borrow_mut());
+    }
+}
+
+/// The framebuffer a pass writes to.
+#[derive(Clone, Copy)]
+enum Output {
+    /// One of the offscreen buffers.
+    Buffer(usize),
+    /// The framebuffer that was bound when the frame began.
+    Final,
+}
+
+/// Draws a scene offscreen, then runs it through a chain of full-screen shader passes,
+/// in order, before writing the result to the framebuffer it was going to.
+///
+/// Attach one to an [`App`] with [`App::set_post_process`], or wrap drawing with
+/// [`PostProcess::render`].
+///
+/// [`App`]: crate::core::App
+/// [`App::set_post_process`]: crate::core::App::set_post_process
+pub struct PostProcess {
+    effects: PostEffects,
+    samples: i32,
+    /// The scene, followed by two ping-pong buffers, all of the viewport size.
+    buffers: Vec<RenderTarget>,
+    quad: FullscreenQuad,
+    /// Framebuffer and viewport to restore and write the result to.
+    output_framebuffer: GLuint,
+    output_viewport: [GLint; 4],
+}
+
+impl PostProcess {
+    pub fn new(effects: Vec<PostEffect>) -> Self {
+        let post_process = Self {
+            effects: PostEffects::default(),
+            samples: 0,
+            buffers: Vec::new(),
+            quad: FullscreenQuad::new(),
+            output_framebuffer: 0,
+            output_viewport: [0, 0, 0, 0],
+        };
+        post_process.effects.set(effects);
+        post_process
+    }
+
+    /// Draws the scene with `samples` samples per pixel, see [`RenderTargetBuilder::samples`].
+    ///
+    /// [`RenderTargetBuilder::samples`]: crate::core::RenderTargetBuilder::samples
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.samples = samples.max(0);
+        self.buffers.clear();
+        self
+    }
+
+    /// A handle to change the effects at runtime.
+    pub fn effects(&self) -> PostEffects {
+        self.effects.clone()
+    }
+
+    /// Runs `draw` against an offscreen scene cleared to `clear_color`, then applies the
+    /// effects, writing the result to the framebuffer bound beforehand.
+    pub fn render(&mut self, clear_color: &Color, draw: impl FnOnce()) {
+        self.begin(clear_color);
+        draw();
+        self.finish();
+    }
+
+    /// Redirects drawing to the scene buffer, resized to the current viewport if needed.
+    fn begin(&mut self, clear_color: &Color) {
+        (self.output_framebuffer, self.output_viewport) = bound_framebuffer();
+        let [_, _, width, height] = self.output_viewport;
+        let (width, height) = (width.max(1), height.max(1));
+
+        if self.buffers.first().map(RenderTarget::size) != Some((width, height)) {
+            self.buffers = vec![
+                RenderTarget::builder(width as u32, height as u32)
+                    .samples(self.samples)
+                    .build(),
+                RenderTarget::new(width as u32, height as u32),
+                RenderTarget::new(width as u32, height as u32),
+            ];
+        }
+
+        gl_bind_framebuffer(GL_FRAMEBUFFER, self.buffers[0].draw_framebuffer());
+        render_state::set_viewport([0, 0, width, height]);
+        gl_clear_color(
+            clear_color.red_value(),
+            clear_color.green_value(),
+            clear_color.blue_value(),
+            clear_color.alpha(),
+        );
+        gl_clear(GL_COLOR_BUFFER_BIT | GL_DEPTH_BUFFER_BIT | GL_STENCIL_BUFFER_BIT);
+    }
+
+    /// Runs the passes from the scene buffer to the output framebuffer.
+    fn finish(&mut self) {
+        self.buffers[0].resolve();
+        // passes overwrite whole buffers
+        render_state::set_blend(None);
+
+        let effects = self.effects.get();
+        let mut current = 0;
+        if effects.is_empty() {
+            self.pass(&[], &[current], Output::Final, |_| {});
+        }
+        for (i, effect) in effects.iter().enumerate() {
+            let last = i + 1 == effects.len();
+            let (a, b) = match current {
+                0 => (1, 2),
+                1 => (2, 0),
+                _ => (0, 1),
+            };
+            let output = |buffer| {
+                if last {
+                    Output::Final
+                } else {
+                    Output::Buffer(buffer)
+                }
+            };
+            match *effect {
+                PostEffect::Blur { radius } => {
+                    self.blur(radius, current, a, output(b));
+                    current = b;
+                }
+                PostEffect::Bloom {
+                    threshold,
+                    intensity,
+                    radius,
+                } => {
+                    self.pass(&["BRIGHT_PASS"], &[current], Output::Buffer(a), |shader| {
+                        shader.set_uniform("u_threshold", threshold);
+                    });
+                    self.blur(radius, a, b, Output::Buffer(a));
+                    self.pass(&["BLOOM_COMPOSITE"], &[current, a], output(b), |shader| {
+                        shader.set_uniform("u_intensity", intensity);
+                    });
+                    current = b;
+                }
+                PostEffect::ColorGrade {
+                    brightness,
+                    contrast,
+                    saturation,
+                } => {
+                    self.pass(&["COLOR_GRADE"], &[current], output(a), |shader| {
+                        shader.set_uniform("u_brightness", brightness);
+                        shader.set_uniform("u_contrast", contrast);
+                        shader.set_uniform("u_saturation", saturation);
+                    });
+                    current = a;
+                }
+                PostEffect::Vignette {
+                    strength,
+                    radius,
+                    softness,
+                } => {
+                    self.pass(&["VIGNETTE"], &[current], output(a), |shader| {
+                        shader.set_uniform("u_strength", strength);
+                        shader.set_uniform("u_radius", radius);
+                        shader.set_uniform("u_softness", softness);
+                    });
+                    current = a;
+                }
+            }
+        }
+
+        gl_bind_framebuffer(GL_FRAMEBUFFER, self.output_framebuffer);
+        let [x, y, width, height] = self.output_viewport;
+        render_state::set_viewport([x, y, width, height]);
+    }
+
+    /// Blurs `input` horizontally into `scratch`, then vertically into `output`.
+    fn blur(&self, radius: f32, input: usize, scratch: usize, output: Output) {
+        let (width, height) = self.buffers[input].size();
+        let texel = Vec2::new(1.0 / width as f32, 1.0 / height as f32);
+        for (from, to, direction) in [
+            (input, Output::Buffer(scratch), Vec2::new(texel.x, 0.0)),
+            (scratch, output, Vec2::new(0.0, texel.y)),
+        ] {
+            self.pass(&["BLUR"], &[from], to, |shader| {
+                shader.set_uniform("u_sigma", radius);
+                shader.set_uniform("u_direction", direction);
+            });
+        }
+    }
+
+    /// Runs the post shader variant `defines` over `inputs` into `output`.
+    fn pass(
+        &self,
+        defines: &[&str],
+        inputs: &[usize],
+        output: Output,
+        set_uniforms: impl FnOnce(&Shader),
+    ) {
+        match output {
+            Output::Buffer(buffer) => {
+                let (width, height) = self.buffers[buffer].size();
+                gl_bind_framebuffer(GL_FRAMEBUFFER, self.buffers[buffer].texture_framebuffer());
+                render_state::set_viewport([0, 0, width, height]);
+            }
+            Output::Final => {
+                let [x, y, width, height] = self.output_viewport;
+                gl_bind_framebuffer(GL_FRAMEBUFFER, self.output_framebuffer);
+                render_state::set_viewport([x, y, width, height]);
+            }
+        }
+
+        let inputs: Vec<&Texture> = inputs
+            .iter()
+            .map(|input| self.buffers[*input].texture().as_ref())
+            .collect();
+        self.quad.draw(defines, &inputs, set_uniforms);
+    }
+}
+
+/// A quad covering the viewport, drawn with a variant of the post-processing shader.
+pub(crate) struct FullscreenQuad {
+    geometry: Geometry,
+}
+
+impl FullscreenQuad {
+    pub(crate) fn new() -> Self {
+        let mut geometry = Geometry::new(GL_TRIANGLE_STRIP);
+        geometry.add_buffer(&[-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0], 2);
+        geometry.add_vertex_attribute(Attribute::new(0, 2, 2, 0));
+        Self { geometry }
+    }
+
+    /// Draws into the bound framebuffer with the shader variant `defines`, sampling
+    /// `inputs` as `u_texture` and then `u_bloom`.
+    pub(crate) fn draw(
+        &self,
+        defines: &[&str],
+        inputs: &[&Texture],
+        set_uniforms: impl FnOnce(&Shader),
+    ) {
+        let shader = POST_SHADERS.with(|variants| {
+            variants
+                .get(defines)
+                .unwrap_or_else(|e| panic!("Failed to compile post shader {defines:?}: {e}"))
+        });
+        shader.use_program();
+        for (unit, (texture, sampler)) in inputs.iter().zip(["u_texture", "u_bloom"]).enumerate() {
+            texture.bind(unit as u32);
+            shader.set_uniform(sampler, unit as i32);
+        }
+        set_uniforms(&shader);
+
+        self.geometry.bind();
+        gl_draw_arrays(GL_TRIANGLE_STRIP, 0, self.geometry.vertex_count());
+        render_state::record_draw(self.geometry.vertex_count() as u64);
+    }
+}
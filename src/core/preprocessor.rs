@@ -0,0 +1,439 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::core::shader::{Shader, ShaderError, ShaderStage};
+
+/// Includes nested deeper than this are assumed to be recursive.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// An `#include` that couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreprocessError {
+    /// No registered include or file in the include directories has this name.
+    IncludeNotFound { name: String, line: usize },
+    /// The include directive isn't of the form `#include "name"` or `#include <name>`.
+    MalformedInclude { line: usize },
+    /// The includes nest more than 32 levels deep, most likely a cycle.
+    IncludeTooDeep { name: String, line: usize },
+}
+
+impl PreprocessError {
+    /// The line of the offending directive, in the file that contains it.
+    pub fn line(&self) -> usize {
+        match self {
+            PreprocessError::IncludeNotFound { line, .. }
+            | PreprocessError::MalformedInclude { line }
+            | PreprocessError::IncludeTooDeep { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for PreprocessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreprocessError::IncludeNotFound { name, line } => {
+                write!(f, "line {line}: include \"{name}\" not found")
+            }
+            PreprocessError::MalformedInclude { line } => {
+                write!(
+                    f,
+                    "line {line}: expected #include \"name\" or #include <name>"
+                )
+            }
+            PreprocessError::IncludeTooDeep { name, line } => {
+                write!(
+                    f,
+                    "line {line}: include \"{name}\" nested too deep, is it recursive?"
+                )
+            }
+        }
+    }
+}
+
+impl Error for PreprocessError {}
+
+/// Resolves `#include` directives and injects `#define`s into GLSL sources.
+///
+/// Includes are looked up in the registry first, then in the include directories in the
+/// order they were added. `#line` directives are emitted around included text so that
+/// compiler errors in the including file keep pointing at the right line; errors inside an
+/// include are reported with its 1-based include index as the source string number.
+#[derive(Debug, Clone, Default)]
+pub struct ShaderPreprocessor {
+    includes: HashMap<String, String>,
+    include_dirs: Vec<PathBuf>,
+}
+
+impl ShaderPreprocessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes `source` available as `#include "name"`.
+    pub fn register_include(&mut self, name: impl Into<String>, source: impl Into<String>) {
+        self.includes.insert(name.into(), source.into());
+    }
+
+    /// Adds a directory in which includes not in the registry are looked up.
+    pub fn add_include_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.include_dirs.push(dir.into());
+    }
+
+    /// Returns `source` with its includes expanded and `defines` inserted right after the
+    /// `#version` directive, which may follow blank lines and comments. A define is either
+    /// `"NAME"` or `"NAME=VALUE"`.
+    pub fn process(&self, source: &str, defines: &[&str]) -> Result<String, PreprocessError> {
+        let mut out = String::with_capacity(source.len());
+
+        // `#version` must stay the first directive
+        let (body, next_line) = match split_version(source) {
+            Some((preamble, body, version_line)) => {
+                out.push_str(preamble);
+                if !preamble.ends_with('\n') {
+                    out.push('\n');
+                }
+                (body, version_line + 1)
+            }
+            None => (source, 1),
+        };
+
+        if !defines.is_empty() {
+            for define in defines {
+                let (name, value) = define.split_once('=').unwrap_or((define, ""));
+                let define = format!("#define {} {}", name.trim(), value.trim());
+                out.push_str(define.trim_end());
+                out.push('\n');
+            }
+            out.push_str(&format!("#line {next_line} 0\n"));
+        }
+
+        let mut include_count = 0;
+        self.expand(body, next_line, 0, 0, &mut include_count, &mut out)?;
+        Ok(out)
+    }
+
+    fn expand(
+        &self,
+        source: &str,
+        first_line: usize,
+        source_index: usize,
+        depth: usize,
+        include_count: &mut usize,
+        out: &mut String,
+    ) -> Result<(), PreprocessError> {
+        for (i, text) in source.lines().enumerate() {
+            let line = first_line + i;
+            let Some(directive) = text.trim_start().strip_prefix("#include") else {
+                out.push_str(text);
+                out.push('\n');
+                continue;
+            };
+
+            let name =
+                parse_include_name(directive).ok_or(PreprocessError::MalformedInclude { line })?;
+            if depth >= MAX_INCLUDE_DEPTH {
+                return Err(PreprocessError::IncludeTooDeep {
+                    name: name.to_string(),
+                    line,
+                });
+            }
+            let included = self
+                .resolve(name)
+                .ok_or_else(|| PreprocessError::IncludeNotFound {
+                    name: name.to_string(),
+                    line,
+                })?;
+
+            *include_count += 1;
+            out.push_str(&format!("#line 1 {include_count}\n"));
+            self.expand(&included, 1, *include_count, depth + 1, include_count, out)?;
+            out.push_str(&format!("#line {} {source_index}\n", line + 1));
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, name: &str) -> Option<String> {
+        if let Some(source) = self.includes.get(name) {
+            return Some(source.clone());
+        }
+        self.include_dirs
+            .iter()
+            .find_map(|dir| fs::read_to_string(dir.join(name)).ok())
+    }
+}
+
+/// Splits `source` right after its `#version` directive, returning the text up to it, the
+/// rest, and the directive's 1-based line. `None` if the first line of code isn't one.
+fn split_version(source: &str) -> Option<(&str, &str, usize)> {
+    let mut offset = 0;
+    let mut in_comment = false;
+    for (i, line) in source.split_inclusive('\n').enumerate() {
+        offset += line.len();
+        let code = strip_comments(line, &mut in_comment);
+        let code = code.trim();
+        if !code.is_empty() {
+            return code
+                .starts_with("#version")
+                .then(|| (&source[..offset], &source[offset..], i + 1));
+        }
+    }
+    None
+}
+
+/// Returns the code of `line` outside comments, `in_block` tracking whether a `/* */`
+/// comment is still open at the end of the line.
+fn strip_comments(line: &str, in_block: &mut bool) -> String {
+    let mut code = String::new();
+    let mut rest = line;
+    loop {
+        if *in_block {
+            let Some(end) = rest.find("*/") else {
+                return code;
+            };
+            rest = &rest[end + 2..];
+            *in_block = false;
+        }
+        match (rest.find("//"), rest.find("/*")) {
+            (Some(line_comment), block) if block.is_none_or(|block| line_comment < block) => {
+                code.push_str(&rest[..line_comment]);
+                return code;
+            }
+            (_, Some(block)) => {
+                code.push_str(&rest[..block]);
+                code.push(' ');
+                rest = &rest[block + 2..];
+                *in_block = true;
+            }
+            _ => {
+                code.push_str(rest);
+                return code;
+            }
+        }
+    }
+}
+
+fn parse_include_name(directive: &str) -> Option<&str> {
+    let directive = directive.trim();
+    let (open, close) = match directive.chars().next()? {
+        '"' => ('"', '"'),
+        '<' => ('<', '>'),
+        _ => return None,
+    };
+    let rest = directive.strip_prefix(open)?;
+    let end = rest.find(close)?;
+    Some(&rest[..end])
+}
+
+/// Compiles and caches variants of one shader, each with a different set of defines.
+///
+/// This lets a single source serve e.g. both the single and the instanced drawing paths,
+/// selected with `#ifdef INSTANCED`.
+pub struct ShaderVariants {
+    vertex_src: String,
+    fragment_src: String,
+    geometry_src: Option<String>,
+    preprocessor: ShaderPreprocessor,
+    cache: RefCell<HashMap<Vec<String>, Rc<Shader>>>,
+}
+
+impl ShaderVariants {
+    pub fn new(vertex_src: &str, fragment_src: &str, geometry_src: Option<&str>) -> Self {
+        Self::with_preprocessor(
+            vertex_src,
+            fragment_src,
+            geometry_src,
+            ShaderPreprocessor::new(),
+        )
+    }
+
+    /// Like [`ShaderVariants::new`], resolving includes with `preprocessor`.
+    pub fn with_preprocessor(
+        vertex_src: &str,
+        fragment_src: &str,
+        geometry_src: Option<&str>,
+        preprocessor: ShaderPreprocessor,
+    ) -> Self {
+        Self {
+            vertex_src: vertex_src.to_string(),
+            fragment_src: fragment_src.to_string(),
+            geometry_src: geometry_src.map(str::to_string),
+            preprocessor,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the variant compiled with `defines`, compiling it on first use. The order of
+    /// the defines doesn't matter.
+    pub fn get(&self, defines: &[&str]) -> Result<Rc<Shader>, ShaderError> {
+        let mut key: Vec<String> = defines.iter().map(|d| d.to_string()).collect();
+        key.sort();
+        key.dedup();
+
+        if let Some(shader) = self.cache.borrow().get(&key) {
+            return Ok(Rc::clone(shader));
+        }
+
+        let defines: Vec<&str> = key.iter().map(String::as_str).collect();
+        let process = |stage: ShaderStage, source: &str| {
+            self.preprocessor
+                .process(source, &defines)
+                .map_err(|err| ShaderError {
+                    stage,
+                    log: err.to_string(),
+                    line: Some(err.line()),
+                    source_line: None,
+                    path: None,
+                })
+        };
+        let vertex = process(ShaderStage::Vertex, &self.vertex_src)?;
+        let fragment = process(ShaderStage::Fragment, &self.fragment_src)?;
+        let geometry = match &self.geometry_src {
+            Some(src) => Some(process(ShaderStage::Geometry, src)?),
+            None => None,
+        };
+
+        let shader =
+            Shader::compile(&vertex, &fragment, geometry.as_deref()).map_err(|mut err| {
+                // the driver's line numbers refer to the sources before preprocessing
+                let original = match err.stage {
+                    ShaderStage::Vertex => Some(self.vertex_src.as_str()),
+                    ShaderStage::Fragment => Some(self.fragment_src.as_str()),
+                    ShaderStage::Geometry => self.geometry_src.as_deref(),
+                    ShaderStage::Link => None,
+                };
+                err.set_source(original);
+                err
+            })?;
+        let shader = Rc::new(shader);
+        self.cache.borrow_mut().insert(key, Rc::clone(&shader));
+        Ok(shader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The (source string, line) each line of `processed` is attributed to by the
+    /// `#line` directives, `None` for the directives themselves.
+    fn origins(processed: &str) -> Vec<Option<(usize, usize)>> {
+        let (mut index, mut line) = (0, 1);
+        processed
+            .lines()
+            .map(|text| {
+                if let Some(directive) = text.strip_prefix("#line ") {
+                    let mut numbers = directive.split(' ').map(|n| n.parse().unwrap());
+                    line = numbers.next().unwrap();
+                    index = numbers.next().unwrap_or(index);
+                    None
+                } else {
+                    line += 1;
+                    Some((index, line - 1))
+                }
+            })
+            .collect()
+    }
+
+    /// The origin of the line of `processed` containing `marker`.
+    fn origin_of(processed: &str, marker: &str) -> Option<(usize, usize)> {
+        let position = processed.lines().position(|text| text.contains(marker))?;
+        origins(processed)[position]
+    }
+
+    #[test]
+    fn defines_follow_version_after_comments() {
+        let source = "// my shader\n/* multi\n   line */\n\n#version 330 core\nvoid main() {}";
+        let processed = ShaderPreprocessor::new().process(source, &["FOO"]).unwrap();
+        assert_eq!(
+            processed,
+            "// my shader\n/* multi\n   line */\n\n#version 330 core\n#define FOO\n#line 6 0\nvoid main() {}\n"
+        );
+        assert_eq!(origin_of(&processed, "main"), Some((0, 6)));
+    }
+
+    #[test]
+    fn version_without_trailing_newline() {
+        let processed = ShaderPreprocessor::new()
+            .process("#version 330 core", &["FOO"])
+            .unwrap();
+        assert_eq!(processed, "#version 330 core\n#define FOO\n#line 2 0\n");
+    }
+
+    #[test]
+    fn defines_without_version() {
+        let processed = ShaderPreprocessor::new()
+            .process("void main() {}\n", &["FOO"])
+            .unwrap();
+        assert_eq!(processed, "#define FOO\n#line 1 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn name_value_defines() {
+        let processed = ShaderPreprocessor::new()
+            .process("#version 330 core\n", &["COUNT=4", " SCALE = 0.5 "])
+            .unwrap();
+        assert_eq!(
+            processed,
+            "#version 330 core\n#define COUNT 4\n#define SCALE 0.5\n#line 2 0\n"
+        );
+    }
+
+    #[test]
+    fn nested_includes() {
+        let mut preprocessor = ShaderPreprocessor::new();
+        preprocessor.register_include(
+            "outer.glsl",
+            "// outer\n#include \"inner.glsl\"\nfloat outer;",
+        );
+        preprocessor.register_include("inner.glsl", "float inner;");
+        let source = "#version 330 core\n#include <outer.glsl>\nvoid main() {}\n";
+        let processed = preprocessor.process(source, &[]).unwrap();
+
+        assert_eq!(origin_of(&processed, "// outer"), Some((1, 1)));
+        assert_eq!(origin_of(&processed, "float inner"), Some((2, 1)));
+        assert_eq!(origin_of(&processed, "float outer"), Some((1, 3)));
+        assert_eq!(origin_of(&processed, "main"), Some((0, 3)));
+    }
+
+    #[test]
+    fn include_cycle() {
+        let mut preprocessor = ShaderPreprocessor::new();
+        preprocessor.register_include("a.glsl", "#include \"b.glsl\"");
+        preprocessor.register_include("b.glsl", "\n#include \"a.glsl\"");
+        let err = preprocessor
+            .process("#include \"a.glsl\"\n", &[])
+            .unwrap_err();
+        assert!(matches!(err, PreprocessError::IncludeTooDeep { .. }));
+    }
+
+    #[test]
+    fn missing_include() {
+        let err = ShaderPreprocessor::new()
+            .process("#version 330 core\n\n#include \"missing.glsl\"\n", &[])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            PreprocessError::IncludeNotFound {
+                name: "missing.glsl".to_string(),
+                line: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn error_lines_map_to_origin() {
+        let mut preprocessor = ShaderPreprocessor::new();
+        preprocessor.register_include("common.glsl", "float a;\nfloat b;\nfloat bad_include;");
+        let source =
+            "// header\n#version 330 core\n#include \"common.glsl\"\nfloat c;\nfloat bad_main;\n";
+        let processed = preprocessor.process(source, &["INSTANCED", "N=2"]).unwrap();
+
+        assert_eq!(origin_of(&processed, "bad_include"), Some((1, 3)));
+        assert_eq!(origin_of(&processed, "bad_main"), Some((0, 5)));
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::core::engine::opengl::{
+    GL_ACTIVE_ATTRIBUTES, GL_ACTIVE_UNIFORMS, GL_BOOL, GL_FLOAT, GL_FLOAT_MAT2, GL_FLOAT_MAT3,
+    GL_FLOAT_MAT4, GL_FLOAT_VEC2, GL_FLOAT_VEC3, GL_FLOAT_VEC4, GL_INT, GL_INT_VEC2, GL_INT_VEC3,
+    GL_INT_VEC4, GL_SAMPLER_2D, GL_UNSIGNED_INT, GL_UNSIGNED_INT_VEC2, GL_UNSIGNED_INT_VEC3,
+    GL_UNSIGNED_INT_VEC4, GLenum, GLint, GLuint, gl_get_active_attrib, gl_get_active_uniform,
+    gl_get_attrib_location, gl_get_programiv, gl_get_uniform_location,
+};
+use crate::core::geometry::Attribute;
+
+/// An active vertex attribute or uniform of a linked shader program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderVariable {
+    pub name: String,
+    /// The GLSL type, as an OpenGL enum such as `GL_FLOAT_VEC2`.
+    pub gl_type: GLenum,
+    /// The attribute or uniform location, `-1` for built-ins and uniform block members.
+    pub location: GLint,
+    /// Number of array elements, 1 for non-arrays.
+    pub size: GLint,
+}
+
+impl ShaderVariable {
+    /// The GLSL name of `gl_type`, e.g. `vec2`.
+    pub fn type_name(&self) -> &'static str {
+        match self.gl_type {
+            GL_FLOAT => "float",
+            GL_FLOAT_VEC2 => "vec2",
+            GL_FLOAT_VEC3 => "vec3",
+            GL_FLOAT_VEC4 => "vec4",
+            GL_INT => "int",
+            GL_INT_VEC2 => "ivec2",
+            GL_INT_VEC3 => "ivec3",
+            GL_INT_VEC4 => "ivec4",
+            GL_UNSIGNED_INT => "uint",
+            GL_UNSIGNED_INT_VEC2 => "uvec2",
+            GL_UNSIGNED_INT_VEC3 => "uvec3",
+            GL_UNSIGNED_INT_VEC4 => "uvec4",
+            GL_BOOL => "bool",
+            GL_FLOAT_MAT2 => "mat2",
+            GL_FLOAT_MAT3 => "mat3",
+            GL_FLOAT_MAT4 => "mat4",
+            GL_SAMPLER_2D => "sampler2D",
+            _ => "unknown",
+        }
+    }
+
+    /// Number of components of a scalar or vector type, `None` for other types.
+    fn components(&self) -> Option<GLint> {
+        match self.gl_type {
+            GL_FLOAT | GL_INT | GL_UNSIGNED_INT | GL_BOOL => Some(1),
+            GL_FLOAT_VEC2 | GL_INT_VEC2 | GL_UNSIGNED_INT_VEC2 => Some(2),
+            GL_FLOAT_VEC3 | GL_INT_VEC3 | GL_UNSIGNED_INT_VEC3 => Some(3),
+            GL_FLOAT_VEC4 | GL_INT_VEC4 | GL_UNSIGNED_INT_VEC4 => Some(4),
+            _ => None,
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(
+            self.gl_type,
+            GL_INT
+                | GL_INT_VEC2
+                | GL_INT_VEC3
+                | GL_INT_VEC4
+                | GL_UNSIGNED_INT
+                | GL_UNSIGNED_INT_VEC2
+                | GL_UNSIGNED_INT_VEC3
+                | GL_UNSIGNED_INT_VEC4
+        )
+    }
+
+    fn is_sampler(&self) -> bool {
+        self.gl_type == GL_SAMPLER_2D
+    }
+}
+
+/// A mismatch between what a shader program reads and what a draw call provides, see
+/// [`Shader::validate`](crate::core::Shader::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The shader reads an attribute that the geometry doesn't provide.
+    MissingAttribute(ShaderVariable),
+    /// The geometry provides the attribute with a different number of components or with
+    /// float instead of integer values (or the opposite).
+    AttributeTypeMismatch {
+        expected: ShaderVariable,
+        components: GLint,
+        integer: bool,
+    },
+    /// The shader reads a uniform that was never set since the program was linked.
+    UnsetUniform(ShaderVariable),
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::MissingAttribute(var) => write!(
+                f,
+                "attribute `{}` ({}, location {}) is not provided by the geometry",
+                var.name,
+                var.type_name(),
+                var.location
+            ),
+            ValidationIssue::AttributeTypeMismatch {
+                expected,
+                components,
+                integer,
+            } => write!(
+                f,
+                "attribute `{}` (location {}) is a {} but the geometry provides {} {} component(s)",
+                expected.name,
+                expected.location,
+                expected.type_name(),
+                components,
+                if *integer { "integer" } else { "float" }
+            ),
+            ValidationIssue::UnsetUniform(var) => {
+                write!(
+                    f,
+                    "uniform `{}` ({}) is never set",
+                    var.name,
+                    var.type_name()
+                )
+            }
+        }
+    }
+}
+
+/// The active attributes and uniforms of a linked program.
+#[derive(Debug, Default)]
+pub(crate) struct Reflection {
+    pub attributes: Vec<ShaderVariable>,
+    pub uniforms: Vec<ShaderVariable>,
+    /// Uniform locations keyed by name, including `name[i]` for each array element.
+    pub uniform_locations: HashMap<String, GLint>,
+}
+
+impl Reflection {
+    pub fn of(program: GLuint) -> Self {
+        let mut reflection = Reflection::default();
+
+        let mut count = 0;
+        gl_get_programiv(program, GL_ACTIVE_ATTRIBUTES, &mut count);
+        for index in 0..count.max(0) as GLuint {
+            let (name, size, gl_type) = gl_get_active_attrib(program, index);
+            let location = gl_get_attrib_location(program, &name);
+            reflection.attributes.push(ShaderVariable {
+                name,
+                gl_type,
+                location,
+                size,
+            });
+        }
+        reflection.attributes.sort_by_key(|var| var.location);
+
+        gl_get_programiv(program, GL_ACTIVE_UNIFORMS, &mut count);
+        for index in 0..count.max(0) as GLuint {
+            let (name, size, gl_type) = gl_get_active_uniform(program, index);
+            let location = gl_get_uniform_location(program, &name);
+            // uniform block members have no location
+            if location != -1 {
+                // arrays are reported as `name[0]`, make them reachable as `name` too
+                if let Some(base) = name.strip_suffix("[0]") {
+                    let locations = &mut reflection.uniform_locations;
+                    locations.insert(base.to_string(), location);
+                    for i in 1..size {
+                        let element = format!("{base}[{i}]");
+                        let location = gl_get_uniform_location(program, &element);
+                        if location != -1 {
+                            locations.insert(element, location);
+                        }
+                    }
+                }
+                reflection.uniform_locations.insert(name.clone(), location);
+            }
+            reflection.uniforms.push(ShaderVariable {
+                name,
+                gl_type,
+                location,
+                size,
+            });
+        }
+        reflection.uniforms.sort_by_key(|var| var.location);
+
+        reflection
+    }
+
+    /// Checks `attributes` and the uniforms set so far against the program's inputs.
+    pub fn validate(
+        &self,
+        attributes: &[Attribute],
+        is_uniform_set: impl Fn(GLint) -> bool,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        // built-ins such as gl_VertexID have no location
+        for var in self.attributes.iter().filter(|var| var.location >= 0) {
+            let provided = attributes
+                .iter()
+                .find(|attr| attr.location == var.location as GLuint);
+            match (provided, var.components()) {
+                (None, _) => issues.push(ValidationIssue::MissingAttribute(var.clone())),
+                (Some(attr), Some(components))
+                    if attr.size != components || attr.integer != var.is_integer() =>
+                {
+                    issues.push(ValidationIssue::AttributeTypeMismatch {
+                        expected: var.clone(),
+                        components: attr.size,
+                        integer: attr.integer,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        // samplers default to texture unit 0, which is usually what's wanted
+        for var in &self.uniforms {
+            if var.location >= 0 && !var.is_sampler() && !is_uniform_set(var.location) {
+                issues.push(ValidationIssue::UnsetUniform(var.clone()));
+            }
+        }
+
+        issues
+    }
+}
@@ -0,0 +1,229 @@
+use std::cell::RefCell;
+use std::ffi::c_void;
+
+use crate::core::blend::BlendFunc;
+use crate::core::engine::opengl::{
+    GL_BLEND, GL_TEXTURE_2D, GL_TEXTURE0, GL_VIEWPORT, GLfloat, GLint, GLuint, gl_active_texture,
+    gl_bind_texture, gl_bind_vertex_array, gl_blend_equation, gl_blend_func_separate, gl_disable,
+    gl_enable, gl_get_integerv, gl_point_size, gl_use_program, gl_viewport,
+};
+
+/// Texture units tracked by the cache, those guaranteed to be available to fragment
+/// shaders by OpenGL 3.3.
+pub(crate) const MAX_TEXTURE_UNITS: usize = 16;
+
+/// Counters of the GL work done during a frame, see [`Renderer::frame_stats`].
+///
+/// [`Renderer::frame_stats`]: crate::core::Renderer::frame_stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    pub draw_calls: u32,
+    /// Vertices submitted, counting each instance.
+    pub vertices: u64,
+    pub program_switches: u32,
+    pub vertex_array_binds: u32,
+    pub texture_binds: u32,
+    /// Blend, point size and viewport changes.
+    pub state_changes: u32,
+    /// State changes skipped because the state was already set.
+    pub redundant_changes_skipped: u32,
+}
+
+/// The GL state last set through this module, `None` when unknown.
+#[derive(Default)]
+struct RenderState {
+    program: Option<GLuint>,
+    vertex_array: Option<GLuint>,
+    active_unit: Option<u32>,
+    textures: [Option<GLuint>; MAX_TEXTURE_UNITS],
+    blend: Option<Option<BlendFunc>>,
+    point_size: Option<GLfloat>,
+    viewport: Option<[GLint; 4]>,
+    frame: FrameStats,
+    last_frame: FrameStats,
+}
+
+thread_local! {
+    // GL contexts are current on one thread, so is the state describing them
+    static STATE: RefCell<RenderState> = RefCell::new(RenderState::default());
+}
+
+/// Sets `slot` to `value` and returns true if it changed, counting skipped changes.
+fn update<T: PartialEq>(slot: &mut Option<T>, value: T, skipped: &mut u32) -> bool {
+    if slot.as_ref() == Some(&value) {
+        *skipped += 1;
+        false
+    } else {
+        *slot = Some(value);
+        true
+    }
+}
+
+fn with_state<R>(f: impl FnOnce(&mut RenderState) -> R) -> R {
+    STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+pub(crate) fn use_program(program: GLuint) {
+    with_state(|s| {
+        if update(
+            &mut s.program,
+            program,
+            &mut s.frame.redundant_changes_skipped,
+        ) {
+            gl_use_program(program);
+            s.frame.program_switches += 1;
+        }
+    })
+}
+
+pub(crate) fn bind_vertex_array(vertex_array: GLuint) {
+    with_state(|s| {
+        if update(
+            &mut s.vertex_array,
+            vertex_array,
+            &mut s.frame.redundant_changes_skipped,
+        ) {
+            gl_bind_vertex_array(vertex_array);
+            s.frame.vertex_array_binds += 1;
+        }
+    })
+}
+
+/// Binds `texture` to `unit`, leaving `unit` active.
+pub(crate) fn bind_texture(unit: u32, texture: GLuint) {
+    assert!(
+        (unit as usize) < MAX_TEXTURE_UNITS,
+        "texture unit {unit} is out of range, at most {MAX_TEXTURE_UNITS} are supported"
+    );
+    with_state(|s| {
+        let skipped = &mut s.frame.redundant_changes_skipped;
+        if update(&mut s.active_unit, unit, skipped) {
+            gl_active_texture(GL_TEXTURE0 + unit);
+        }
+        if update(&mut s.textures[unit as usize], texture, skipped) {
+            gl_bind_texture(GL_TEXTURE_2D, texture);
+            s.frame.texture_binds += 1;
+        }
+    })
+}
+
+/// Enables blending with `func`, or disables it.
+pub(crate) fn set_blend(func: Option<BlendFunc>) {
+    with_state(|s| {
+        let previous = s.blend;
+        if !update(&mut s.blend, func, &mut s.frame.redundant_changes_skipped) {
+            return;
+        }
+        match (previous, func) {
+            (Some(Some(_)), None) | (None, None) => gl_disable(GL_BLEND),
+            (Some(None) | None, Some(_)) => gl_enable(GL_BLEND),
+            _ => {}
+        }
+        if let Some(func) = func {
+            let previous = previous.flatten();
+            if previous.map(|p| p.equation) != Some(func.equation) {
+                gl_blend_equation(func.equation);
+            }
+            if previous != Some(func) {
+                gl_blend_func_separate(func.src_rgb, func.dst_rgb, func.src_alpha, func.dst_alpha);
+            }
+        }
+        s.frame.state_changes += 1;
+    })
+}
+
+pub(crate) fn set_point_size(size: GLfloat) {
+    with_state(|s| {
+        if update(
+            &mut s.point_size,
+            size,
+            &mut s.frame.redundant_changes_skipped,
+        ) {
+            gl_point_size(size);
+            s.frame.state_changes += 1;
+        }
+    })
+}
+
+pub(crate) fn set_viewport(viewport: [GLint; 4]) {
+    with_state(|s| {
+        if update(
+            &mut s.viewport,
+            viewport,
+            &mut s.frame.redundant_changes_skipped,
+        ) {
+            let [x, y, width, height] = viewport;
+            gl_viewport(x, y, width, height);
+            s.frame.state_changes += 1;
+        }
+    })
+}
+
+/// The current viewport, queried from GL only when unknown.
+pub(crate) fn viewport() -> [GLint; 4] {
+    with_state(|s| {
+        *s.viewport.get_or_insert_with(|| {
+            let mut viewport = [0, 0, 0, 0];
+            gl_get_integerv(GL_VIEWPORT, viewport.as_mut_ptr() as *mut c_void);
+            viewport
+        })
+    })
+}
+
+/// Records a draw call of `vertices` vertices, instances included.
+pub(crate) fn record_draw(vertices: u64) {
+    with_state(|s| {
+        s.frame.draw_calls += 1;
+        s.frame.vertices += vertices;
+    })
+}
+
+/// Starts counting a new frame, keeping the counters of the one that ended.
+pub(crate) fn end_frame() {
+    with_state(|s| s.last_frame = std::mem::take(&mut s.frame))
+}
+
+/// The counters of the last completed frame.
+pub(crate) fn last_frame_stats() -> FrameStats {
+    with_state(|s| s.last_frame)
+}
+
+/// Forgets `program` before it is deleted, as GL may reuse its name.
+pub(crate) fn forget_program(program: GLuint) {
+    with_state(|s| {
+        if s.program == Some(program) {
+            s.program = None;
+        }
+    })
+}
+
+pub(crate) fn forget_vertex_array(vertex_array: GLuint) {
+    with_state(|s| {
+        if s.vertex_array == Some(vertex_array) {
+            s.vertex_array = None;
+        }
+    })
+}
+
+pub(crate) fn forget_texture(texture: GLuint) {
+    with_state(|s| {
+        for bound in s.textures.iter_mut() {
+            if *bound == Some(texture) {
+                *bound = None;
+            }
+        }
+    })
+}
+
+/// Marks all the state as unknown, so that it is set again on next use.
+pub(crate) fn invalidate() {
+    with_state(|s| {
+        let frame = s.frame;
+        let last_frame = s.last_frame;
+        *s = RenderState {
+            frame,
+            last_frame,
+            ..RenderState::default()
+        };
+    })
+}
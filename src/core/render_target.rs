@@ -0,0 +1,247 @@
+use std::ffi::c_void;
+use std::rc::Rc;
+
+use crate::core::engine::opengl::{
+    GL_COLOR_ATTACHMENT0, GL_COLOR_BUFFER_BIT, GL_DEPTH_STENCIL_ATTACHMENT, GL_DEPTH24_STENCIL8,
+    GL_DRAW_FRAMEBUFFER, GL_DRAW_FRAMEBUFFER_BINDING, GL_FRAMEBUFFER, GL_FRAMEBUFFER_COMPLETE,
+    GL_NEAREST, GL_READ_FRAMEBUFFER, GL_RENDERBUFFER, GL_TEXTURE_2D, GLenum, GLint, GLuint,
+    gl_bind_framebuffer, gl_bind_renderbuffer, gl_blit_framebuffer, gl_check_framebuffer_status,
+    gl_delete_framebuffer, gl_delete_renderbuffer, gl_framebuffer_renderbuffer,
+    gl_framebuffer_texture_2d, gl_gen_framebuffer, gl_gen_renderbuffer, gl_get_integerv,
+    gl_renderbuffer_storage_multisample,
+};
+use crate::core::render_state;
+use crate::core::texture::{Texture, TextureOptions, TextureWrap};
+
+/// Configures a [`RenderTarget`] before creating it.
+#[derive(Debug, Clone)]
+pub struct RenderTargetBuilder {
+    width: u32,
+    height: u32,
+    samples: i32,
+    depth_stencil: bool,
+    texture_options: TextureOptions,
+}
+
+impl RenderTargetBuilder {
+    /// Renders with `samples` samples per pixel, resolved into the texture after drawing.
+    /// `0` disables multisampling.
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.samples = samples.max(0);
+        self
+    }
+
+    /// Adds a depth and stencil buffer, e.g. for stencil-based clipping.
+    pub fn depth_stencil(mut self, enabled: bool) -> Self {
+        self.depth_stencil = enabled;
+        self
+    }
+
+    /// Sets how the color texture is stored and sampled. Defaults to RGBA8, clamped to
+    /// the edges, linearly filtered and without mipmaps.
+    pub fn texture_options(mut self, options: TextureOptions) -> Self {
+        self.texture_options = options;
+        self
+    }
+
+    /// Creates the framebuffer, panicking if the driver doesn't support the configuration
+    /// (e.g. more samples than `GL_MAX_SAMPLES`).
+    pub fn build(self) -> RenderTarget {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let texture = Rc::new(Texture::empty(
+            self.width,
+            self.height,
+            self.texture_options,
+        ));
+        let mut renderbuffers = Vec::new();
+
+        let fbo = gl_gen_framebuffer();
+        gl_bind_framebuffer(GL_FRAMEBUFFER, fbo);
+        gl_framebuffer_texture_2d(
+            GL_FRAMEBUFFER,
+            GL_COLOR_ATTACHMENT0,
+            GL_TEXTURE_2D,
+            texture.id(),
+            0,
+        );
+
+        let mut msaa_fbo = 0;
+        if self.samples > 0 {
+            // the texture only receives the resolved image, drawing goes to renderbuffers
+            check_status(fbo);
+            msaa_fbo = gl_gen_framebuffer();
+            gl_bind_framebuffer(GL_FRAMEBUFFER, msaa_fbo);
+            let color = texture.options().format.internal_format() as GLenum;
+            renderbuffers.push(attach_renderbuffer(
+                GL_COLOR_ATTACHMENT0,
+                color,
+                self.samples,
+                width,
+                height,
+            ));
+        }
+        if self.depth_stencil {
+            renderbuffers.push(attach_renderbuffer(
+                GL_DEPTH_STENCIL_ATTACHMENT,
+                GL_DEPTH24_STENCIL8,
+                self.samples,
+                width,
+                height,
+            ));
+        }
+        check_status(if msaa_fbo != 0 { msaa_fbo } else { fbo });
+        gl_bind_framebuffer(GL_FRAMEBUFFER, 0);
+
+        RenderTarget {
+            width: self.width,
+            height: self.height,
+            samples: self.samples,
+            texture,
+            fbo,
+            msaa_fbo,
+            renderbuffers,
+        }
+    }
+}
+
+/// Attaches a new renderbuffer to the bound framebuffer.
+fn attach_renderbuffer(
+    attachment: GLenum,
+    format: GLenum,
+    samples: i32,
+    width: i32,
+    height: i32,
+) -> GLuint {
+    let renderbuffer = gl_gen_renderbuffer();
+    gl_bind_renderbuffer(GL_RENDERBUFFER, renderbuffer);
+    gl_renderbuffer_storage_multisample(GL_RENDERBUFFER, samples, format, width, height);
+    gl_bind_renderbuffer(GL_RENDERBUFFER, 0);
+    gl_framebuffer_renderbuffer(GL_FRAMEBUFFER, attachment, GL_RENDERBUFFER, renderbuffer);
+    renderbuffer
+}
+
+/// The framebuffer currently drawn into and the viewport, to restore after drawing
+/// elsewhere.
+pub(crate) fn bound_framebuffer() -> (GLuint, [GLint; 4]) {
+    let mut framebuffer: GLint = 0;
+    gl_get_integerv(
+        GL_DRAW_FRAMEBUFFER_BINDING,
+        &mut framebuffer as *mut GLint as *mut c_void,
+    );
+    (framebuffer as GLuint, render_state::viewport())
+}
+
+fn check_status(framebuffer: GLuint) {
+    let status = gl_check_framebuffer_status(GL_FRAMEBUFFER);
+    if status != GL_FRAMEBUFFER_COMPLETE {
+        gl_bind_framebuffer(GL_FRAMEBUFFER, 0);
+        panic!("Framebuffer {framebuffer} is incomplete, status 0x{status:X}");
+    }
+}
+
+/// An offscreen framebuffer whose color buffer is a [`Texture`].
+///
+/// Draw into it with [`Renderer::with_target`], then use [`RenderTarget::texture`] like
+/// any other texture, e.g. with [`ShapeRenderable::from_render_target`]. The texture's
+/// first row is the bottom of the rendered image, as usual in OpenGL.
+///
+/// [`Renderer::with_target`]: crate::core::Renderer::with_target
+/// [`ShapeRenderable::from_render_target`]: crate::graphics2d::shapes::ShapeRenderable::from_render_target
+pub struct RenderTarget {
+    width: u32,
+    height: u32,
+    samples: i32,
+    texture: Rc<Texture>,
+    /// Framebuffer with `texture` attached, also the resolve target when multisampled.
+    fbo: GLuint,
+    /// Multisampled framebuffer drawn into instead of `fbo`, 0 without MSAA.
+    msaa_fbo: GLuint,
+    renderbuffers: Vec<GLuint>,
+}
+
+impl RenderTarget {
+    /// Creates a `width` x `height` RGBA8 target without depth buffer or multisampling.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::builder(width, height).build()
+    }
+
+    pub fn builder(width: u32, height: u32) -> RenderTargetBuilder {
+        assert!(
+            width > 0 && height > 0,
+            "Render target size must be positive"
+        );
+        RenderTargetBuilder {
+            width,
+            height,
+            samples: 0,
+            depth_stencil: false,
+            texture_options: TextureOptions::default()
+                .wrap(TextureWrap::ClampToEdge)
+                .mipmaps(false),
+        }
+    }
+
+    /// The texture holding the rendered image.
+    pub fn texture(&self) -> &Rc<Texture> {
+        &self.texture
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn size(&self) -> (i32, i32) {
+        (self.width as i32, self.height as i32)
+    }
+
+    pub fn samples(&self) -> i32 {
+        self.samples
+    }
+
+    /// The framebuffer with the texture attached, bypassing multisampling.
+    pub(crate) fn texture_framebuffer(&self) -> GLuint {
+        self.fbo
+    }
+
+    /// The framebuffer that draw calls write to.
+    pub(crate) fn draw_framebuffer(&self) -> GLuint {
+        if self.msaa_fbo != 0 {
+            self.msaa_fbo
+        } else {
+            self.fbo
+        }
+    }
+
+    /// Makes what was drawn available in the texture: resolves the samples and updates
+    /// the mipmaps. Leaves the target's framebuffers bound.
+    pub(crate) fn resolve(&self) {
+        if self.msaa_fbo != 0 {
+            let (width, height) = self.size();
+            gl_bind_framebuffer(GL_READ_FRAMEBUFFER, self.msaa_fbo);
+            gl_bind_framebuffer(GL_DRAW_FRAMEBUFFER, self.fbo);
+            gl_blit_framebuffer(
+                [0, 0, width, height],
+                [0, 0, width, height],
+                GL_COLOR_BUFFER_BIT,
+                GL_NEAREST as GLenum,
+            );
+        }
+        self.texture.update_mipmaps();
+    }
+}
+
+impl Drop for RenderTarget {
+    fn drop(&mut self) {
+        for renderbuffer in &self.renderbuffers {
+            gl_delete_renderbuffer(*renderbuffer);
+        }
+        if self.msaa_fbo != 0 {
+            gl_delete_framebuffer(self.msaa_fbo);
+        }
+        gl_delete_framebuffer(self.fbo);
+    }
+}
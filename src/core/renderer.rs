@@ -1,16 +1,29 @@
 use crate::core::engine::glfw::glfw_get_time;
-use crate::core::engine::opengl::{gl_active_texture, gl_bind_texture, gl_blend_func, gl_draw_arrays_instanced, gl_enable, gl_get_integerv, gl_uniform_3f, GL_BLEND, GL_ONE_MINUS_SRC_ALPHA, GL_SRC_ALPHA, GL_TEXTURE0, GL_TEXTURE_2D, GL_VIEWPORT};
+use crate::core::engine::opengl::gl_draw_arrays_instanced;
+use crate::core::engine::opengl::{gl_pixel_storei, gl_read_pixels, GL_PACK_ALIGNMENT, GL_RGBA, GL_UNSIGNED_BYTE};
+use crate::core::image::{save_image, Image};
+use crate::core::geometry::Geometry;
 use crate::core::mesh::Mesh;
 use std::ffi::c_void;
-use crate::core::engine::opengl::{
-    gl_draw_arrays, gl_get_uniform_location, gl_point_size, gl_uniform_matrix_4fv, GLboolean,
-    GLfloat,
-};
+use crate::core::engine::opengl::{gl_draw_arrays, GLfloat};
+use crate::core::engine::opengl::{gl_draw_elements, gl_draw_elements_instanced};
+use crate::core::engine::opengl::{gl_bind_framebuffer, gl_clear, gl_clear_color, GL_COLOR_BUFFER_BIT, GL_DEPTH_BUFFER_BIT, GL_FRAMEBUFFER, GL_STENCIL_BUFFER_BIT};
+use crate::core::color::Color;
+use crate::core::render_target::{RenderTarget, bound_framebuffer};
+use crate::core::render_state::{self, FrameStats, MAX_TEXTURE_UNITS};
+use glam::{Vec2, Vec4};
 use crate::core::window::WindowHandle;
+use crate::core::engine::opengl::GLuint;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 
 pub struct Renderer {
     pub zoom_level: f32,
-    pub window_handle: WindowHandle
+    pub window_handle: WindowHandle,
+    /// (program, vao) pairs already validated, so that issues are reported once per mesh.
+    validated: RefCell<HashSet<(GLuint, GLuint)>>,
+    /// Size of the render target being drawn into by [`Renderer::with_target`].
+    target_size: Cell<Option<(i32, i32)>>,
 }
 pub trait Renderable {
     fn render(&mut self, renderer: &Renderer);
@@ -18,111 +31,231 @@ pub trait Renderable {
 
 impl Renderer {
     pub fn new(window_handle: WindowHandle) -> Self {
-        Renderer {zoom_level:1.0, window_handle}
+        Renderer {
+            zoom_level: 1.0,
+            window_handle,
+            validated: RefCell::new(HashSet::new()),
+            target_size: Cell::new(None),
+        }
     }
 
     pub fn set_point_size(&self, point_size: GLfloat) {
-        gl_point_size(point_size);
+        render_state::set_point_size(point_size);
     }
 
     pub fn viewport_size(&self) -> (i32, i32) {
-        let mut viewport = [0, 0, 0, 0];
-        gl_get_integerv(GL_VIEWPORT, viewport.as_mut_ptr() as *mut c_void);
+        let viewport = render_state::viewport();
         (viewport[2], viewport[3]) // width, height
     }
 
+    /// The draw calls and state changes of the last completed frame, i.e. up to the last
+    /// [`Window::swap_buffers`](crate::core::Window::swap_buffers).
+    pub fn frame_stats(&self) -> FrameStats {
+        render_state::last_frame_stats()
+    }
+
+    /// Forgets the GL state the renderer assumes, after it was changed by raw calls from
+    /// [`engine::opengl`](crate::core::engine::opengl) (program, vertex array, textures,
+    /// blending, point size or viewport).
+    pub fn reset_state_cache(&self) {
+        render_state::invalidate();
+    }
+
+    /// Size in pixels of what is being drawn into: the current render target inside
+    /// [`Renderer::with_target`], the window otherwise.
+    pub fn surface_size(&self) -> (i32, i32) {
+        self.target_size
+            .get()
+            .unwrap_or_else(|| self.window_handle.size())
+    }
+
+    /// Clears the color, depth and stencil buffers of what is being drawn into.
+    pub fn clear(&self, color: &Color) {
+        gl_clear_color(color.red_value(), color.green_value(), color.blue_value(), color.alpha());
+        gl_clear(GL_COLOR_BUFFER_BIT | GL_DEPTH_BUFFER_BIT | GL_STENCIL_BUFFER_BIT);
+    }
+
+    /// Runs `draw` with all drawing redirected to `target`, then resolves the target's
+    /// texture and restores the previous framebuffer and viewport. Calls can be nested.
+    ///
+    /// Shapes are laid out in the target's pixel coordinates, see [`Renderer::surface_size`].
+    pub fn with_target<R>(&self, target: &RenderTarget, draw: impl FnOnce(&Renderer) -> R) -> R {
+        let (previous_framebuffer, viewport) = bound_framebuffer();
+        let previous_size = self.target_size.replace(Some(target.size()));
+
+        let (width, height) = target.size();
+        gl_bind_framebuffer(GL_FRAMEBUFFER, target.draw_framebuffer());
+        render_state::set_viewport([0, 0, width, height]);
+
+        let result = draw(self);
+
+        target.resolve();
+        gl_bind_framebuffer(GL_FRAMEBUFFER, previous_framebuffer);
+        render_state::set_viewport(viewport);
+        self.target_size.set(previous_size);
+        result
+    }
+
+    /// Reads back a `width` x `height` block of RGBA pixels from the framebuffer.
+    ///
+    /// `x` and `y` are framebuffer pixel coordinates with a top-left origin, and the returned
+    /// image's first row is the top row of the block.
+    pub fn read_pixels(&self, x: i32, y: i32, width: i32, height: i32) -> Image {
+        let (_, viewport_height) = self.viewport_size();
+        let width = width.max(0);
+        let height = height.max(0);
+        let mut image = Image {
+            width: width as u32,
+            height: height as u32,
+            pixels: vec![0; width as usize * height as usize * 4],
+        };
+
+        gl_pixel_storei(GL_PACK_ALIGNMENT, 1);
+        gl_read_pixels(
+            x,
+            viewport_height - y - height,
+            width,
+            height,
+            GL_RGBA as u32,
+            GL_UNSIGNED_BYTE,
+            image.pixels.as_mut_ptr() as *mut c_void,
+        );
+        // OpenGL returns rows bottom-up
+        image.flip_vertical();
+        image
+    }
+
+    /// Saves the current framebuffer contents to `path`, the image format is deduced from the
+    /// file extension (e.g. `.png`).
+    ///
+    /// Call this after rendering and before swapping buffers.
+    pub fn save_screenshot(&self, path: &str) -> image::ImageResult<()> {
+        let (width, height) = self.viewport_size();
+        save_image(&self.read_pixels(0, 0, width, height), path)
+    }
+
     pub fn get_time(&self) -> f64 {
         glfw_get_time()
     }
 
     pub fn draw_mesh(&self, mesh: &Mesh) {
-        mesh.shader.use_program();
-        mesh.geometry.bind();
-
-        gl_enable(GL_BLEND);
-        gl_blend_func(GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA);
+        let (ox, oy) = mesh.screen_offset();
+        self.prepare(mesh, Vec2::new(ox, oy));
 
-        let transform_loc = gl_get_uniform_location(mesh.shader.program(), "u_Transform");
-        if transform_loc != -1 {
-            gl_uniform_matrix_4fv(
-                transform_loc,
-                1,
-                GLboolean::FALSE,
-                mesh.transform().to_cols_array().as_ptr(),
+        let geometry = &mesh.geometry;
+        if geometry.is_indexed() {
+            gl_draw_elements(
+                geometry.drawing_mode(),
+                geometry.index_count(),
+                geometry.index_type(),
+                0,
             );
+        } else {
+            gl_draw_arrays(geometry.drawing_mode(), 0, geometry.vertex_count());
         }
-        
-        let offset_loc = gl_get_uniform_location(mesh.shader.program(), "u_screen_offset");
-        if offset_loc != -1 {
-            let (ox, oy) = mesh.screen_offset();
-            crate::core::engine::opengl::gl_uniform_2f(offset_loc, ox, oy);
-        }
-        
-        let color_loc = gl_get_uniform_location(mesh.shader.program(), "geometryColor");
-        if color_loc != -1 {
-            if let Some(color) = mesh.color.as_ref() {
-                gl_uniform_3f(color_loc, color.red_value(), color.green_value(), color.blue_value());
-            }
-        }
+        render_state::record_draw(Self::submitted_vertices(geometry) as u64);
+    }
 
-        if let Some(texture_id) = mesh.texture {
-            gl_active_texture(GL_TEXTURE0);
-            gl_bind_texture(GL_TEXTURE_2D, texture_id);
-        }
+    pub fn draw_mesh_instanced(&self, mesh: &Mesh) {
+        // instanced path uses attribute aInstanceXY → force u_offset = (0,0)
+        self.prepare(mesh, Vec2::ZERO);
 
-        gl_draw_arrays(
-            mesh.geometry.drawing_mode(),
-            0,
-            mesh.geometry.vertex_count(),
-        );
-        mesh.geometry.unbind();
-        if mesh.texture.is_some() {
-            gl_bind_texture(GL_TEXTURE_2D, 0);
+        let geometry = &mesh.geometry;
+        let instance_count = geometry.instance_count().max(0);
+        if geometry.is_indexed() {
+            gl_draw_elements_instanced(
+                geometry.drawing_mode(),
+                geometry.index_count(),
+                geometry.index_type(),
+                0,
+                instance_count,
+            );
+        } else {
+            gl_draw_arrays_instanced(
+                geometry.drawing_mode(),
+                0,
+                geometry.vertex_count(),
+                instance_count,
+            );
         }
+        render_state::record_draw(
+            Self::submitted_vertices(geometry) as u64 * instance_count as u64,
+        );
     }
 
-    pub fn draw_mesh_instanced(&self, mesh: &Mesh) {
+    /// Sets up the GL state to draw `mesh`, leaving it bound afterwards: state that is
+    /// already set is skipped, see [`Renderer::frame_stats`].
+    fn prepare(&self, mesh: &Mesh, screen_offset: Vec2) {
         mesh.shader.use_program();
         mesh.geometry.bind();
+        render_state::set_blend(Some(mesh.blend_mode().func()));
 
-        gl_enable(GL_BLEND);
-        gl_blend_func(GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA);
-
-        let transform_loc = gl_get_uniform_location(mesh.shader.program(), "u_Transform");
-        if transform_loc != -1 {
-            gl_uniform_matrix_4fv(
-                transform_loc, 1, GLboolean::FALSE, mesh.transform().to_cols_array().as_ptr(),
-            );
+        self.apply_uniforms(mesh, screen_offset);
+        if cfg!(debug_assertions) {
+            self.validate(mesh);
         }
+        self.bind_textures(mesh);
+    }
 
-        // instanced path uses attribute aInstanceXY → force u_offset = (0,0)
-        let off_loc = gl_get_uniform_location(mesh.shader.program(), "u_screen_offset");
-        if off_loc != -1 {
-            crate::core::engine::opengl::gl_uniform_2f(off_loc, 0.0, 0.0);
+    fn submitted_vertices(geometry: &Geometry) -> i32 {
+        if geometry.is_indexed() {
+            geometry.index_count()
+        } else {
+            geometry.vertex_count()
         }
+    }
 
-        let color_loc = gl_get_uniform_location(mesh.shader.program(), "geometryColor");
-        if color_loc != -1 {
-            if let Some(color) = mesh.color.as_ref() {
-                gl_uniform_3f(color_loc, color.red_value(), color.green_value(), color.blue_value());
+    /// Reports, once per shader and geometry pair, the mismatches between the mesh's
+    /// geometry and uniforms and what its shader expects.
+    fn validate(&self, mesh: &Mesh) {
+        let key = (mesh.shader.program(), mesh.geometry.vao());
+        if !self.validated.borrow_mut().insert(key) {
+            return;
+        }
+        let issues = mesh.shader.validate(&mesh.geometry);
+        if !issues.is_empty() {
+            eprintln!(
+                "sky_renderer: mesh (program {}, vao {}) doesn't match its shader:",
+                key.0, key.1
+            );
+            for issue in issues {
+                eprintln!("  - {issue}");
             }
         }
+    }
 
-        if let Some(texture_id) = mesh.texture {
-            gl_active_texture(GL_TEXTURE0);
-            gl_bind_texture(GL_TEXTURE_2D, texture_id);
+    /// Uploads the built-in uniforms and the mesh's own uniforms, the mesh's program must be
+    /// in use.
+    fn apply_uniforms(&self, mesh: &Mesh, screen_offset: Vec2) {
+        let shader = &mesh.shader;
+        shader.set_uniform("u_Transform", mesh.transform());
+        shader.set_uniform("u_screen_offset", screen_offset);
+        if let Some(color) = mesh.color.as_ref() {
+            shader.set_uniform(
+                "geometryColor",
+                Vec4::new(
+                    color.red_value(),
+                    color.green_value(),
+                    color.blue_value(),
+                    color.alpha(),
+                ),
+            );
+        }
+        for (name, value) in mesh.uniforms() {
+            shader.set_uniform(name, value);
         }
+    }
 
-        gl_draw_arrays_instanced(
-            mesh.geometry.drawing_mode(),
-            0,
-            mesh.geometry.vertex_count(),
-            mesh.geometry.instance_count().max(0),
+    /// Binds the mesh's textures to consecutive units and points their samplers at them.
+    fn bind_textures(&self, mesh: &Mesh) {
+        let textures = mesh.textures();
+        assert!(
+            textures.len() <= MAX_TEXTURE_UNITS,
+            "a mesh can bind at most {MAX_TEXTURE_UNITS} textures"
         );
-
-        mesh.geometry.unbind();
-        if mesh.texture.is_some() {
-            gl_bind_texture(GL_TEXTURE_2D, 0);
+        for (unit, binding) in textures.iter().enumerate() {
+            binding.texture.bind(unit as u32);
+            mesh.shader.set_uniform(&binding.sampler, unit as i32);
         }
     }
 }
@@ -1,17 +1,207 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
 use crate::core::engine::opengl::{
-    GLuint, gl_attach_shader, gl_compile_shader, gl_create_fragment_shader,
+    GLint, GLuint, gl_attach_shader, gl_compile_shader, gl_create_fragment_shader,
     gl_create_geometry_shader, gl_create_program, gl_create_vertex_shader, gl_delete_program,
-    gl_delete_shader, gl_link_program, gl_shader_source, gl_use_program,
+    gl_delete_shader, gl_get_program_info_log, gl_get_program_link_status,
+    gl_get_shader_compile_status, gl_get_shader_info_log, gl_link_program, gl_shader_source,
 };
+use crate::core::geometry::Geometry;
+use crate::core::reflection::{Reflection, ShaderVariable, ValidationIssue};
+use crate::core::render_state;
+use crate::core::uniform::UniformValue;
+
+/// The step of building a shader program that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Geometry,
+    /// Linking the compiled stages into a program.
+    Link,
+}
+
+impl fmt::Display for ShaderStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ShaderStage::Vertex => "vertex",
+            ShaderStage::Fragment => "fragment",
+            ShaderStage::Geometry => "geometry",
+            ShaderStage::Link => "link",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A shader that failed to compile or link, with the driver's info log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderError {
+    pub stage: ShaderStage,
+    /// The info log reported by the driver, verbatim.
+    pub log: String,
+    /// 1-based line of the first error in the stage's source, if the log mentions one.
+    pub line: Option<usize>,
+    /// The source text of `line`.
+    pub source_line: Option<String>,
+    /// The file the failing stage was read from, for [`ShaderSource::File`] stages.
+    pub path: Option<PathBuf>,
+}
+
+impl ShaderError {
+    fn new(stage: ShaderStage, log: String, source: Option<&str>) -> Self {
+        let mut err = Self {
+            stage,
+            line: parse_error_location(&log).map(|(_, line)| line),
+            log,
+            source_line: None,
+            path: None,
+        };
+        err.set_source(source);
+        err
+    }
+
+    /// Fills in `source_line` from the stage's source, for errors in the main source string
+    /// (as opposed to an included one).
+    pub(crate) fn set_source(&mut self, source: Option<&str>) {
+        self.source_line = match (parse_error_location(&self.log), source) {
+            (Some((0, line)), Some(source)) => source
+                .lines()
+                .nth(line.saturating_sub(1))
+                .map(|s| s.trim_end().to_string()),
+            _ => None,
+        };
+    }
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}: ", path.display())?;
+        }
+        match self.stage {
+            ShaderStage::Link => write!(f, "shader program failed to link")?,
+            stage => write!(f, "{stage} shader failed to compile")?,
+        }
+        if let Some(line) = self.line {
+            write!(f, " at line {line}")?;
+            if let Some(source_line) = &self.source_line {
+                write!(f, "\n  {line:>4} | {}", source_line.trim())?;
+            }
+        }
+        let log = self.log.trim_end();
+        if !log.is_empty() {
+            write!(f, "\n{log}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ShaderError {}
+
+/// Extracts the source string index and line number of the first error from a driver info
+/// log, or of the first warning if it has no located error.
+///
+/// Understands the common formats: `0:12(5): error` (Mesa), `0(12) : error` (NVIDIA) and
+/// `ERROR: 0:12: ...` (AMD, Intel, Apple).
+fn parse_error_location(log: &str) -> Option<(usize, usize)> {
+    fn leading_number(s: &str) -> Option<(usize, &str)> {
+        let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let n = s[..end].parse().ok()?;
+        Some((n, &s[end..]))
+    }
+
+    // (index, line, is_warning) of each located entry
+    let mut entries = log.lines().filter_map(|entry| {
+        let entry = entry.trim_start();
+        let (entry, mut warning) = match entry.strip_prefix("ERROR:") {
+            Some(rest) => (rest, false),
+            None => match entry.strip_prefix("WARNING:") {
+                Some(rest) => (rest, true),
+                None => (entry, false),
+            },
+        };
+        let (index, rest) = leading_number(entry.trim_start())?;
+        let rest = rest.strip_prefix(':').or_else(|| rest.strip_prefix('('))?;
+        let (line, rest) = leading_number(rest)?;
+        // the severity follows the location, e.g. `(5): warning` or `) : warning`
+        let severity = rest.trim_start_matches(|c: char| {
+            c.is_ascii_digit() || c.is_whitespace() || "():".contains(c)
+        });
+        warning |= severity
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("warning"));
+        Some((index, line, warning))
+    });
+
+    let first = entries.next()?;
+    let error = if first.2 {
+        entries.find(|entry| !entry.2)
+    } else {
+        Some(first)
+    };
+    let (index, line, _) = error.unwrap_or(first);
+    Some((index, line))
+}
+
+/// Where the source of a shader stage comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderSource {
+    /// GLSL source code, typically embedded with `include_str!`.
+    Inline(String),
+    /// A GLSL file, read when the shader is built and again by [`Shader::reload_if_changed`].
+    File(PathBuf),
+}
+
+impl ShaderSource {
+    fn load(&self, stage: ShaderStage) -> Result<String, ShaderError> {
+        match self {
+            ShaderSource::Inline(src) => Ok(src.clone()),
+            ShaderSource::File(path) => fs::read_to_string(path).map_err(|err| ShaderError {
+                stage,
+                log: format!("failed to read {}: {err}", path.display()),
+                line: None,
+                source_line: None,
+                path: Some(path.clone()),
+            }),
+        }
+    }
+
+    fn path(&self) -> Option<&Path> {
+        match self {
+            ShaderSource::Inline(_) => None,
+            ShaderSource::File(path) => Some(path),
+        }
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(self.path()?).and_then(|m| m.modified()).ok()
+    }
+}
 
 pub struct Shader {
-    program: GLuint,
+    program: Cell<GLuint>,
+    /// The active attributes and uniforms, filled in at link time.
+    reflection: RefCell<Reflection>,
+    /// Locations of the uniforms set since link time, tracked in debug builds for
+    /// [`Shader::validate`].
+    set_uniforms: RefCell<HashSet<GLint>>,
+    /// The stages' sources, kept for reloading. Empty for [`Shader::compile`]d shaders.
+    sources: Vec<(ShaderStage, ShaderSource)>,
+    /// Modification times of the file sources when they were last read.
+    modified: RefCell<Vec<Option<SystemTime>>>,
 }
 
 impl Drop for Shader {
     fn drop(&mut self) {
-        if self.program != 0 {
-            gl_delete_program(self.program);
+        if self.program.get() != 0 {
+            render_state::forget_program(self.program.get());
+            gl_delete_program(self.program.get());
         }
     }
 }
@@ -21,61 +211,236 @@ impl Shader {
         vertex_src: &str,
         fragment_src: &str,
         geometry_src: Option<&str>,
-    ) -> Result<Self, String> {
-        let program = gl_create_program();
-
-        let vertex_shader = gl_create_vertex_shader();
-        gl_shader_source(vertex_shader, vertex_src);
-        gl_compile_shader(vertex_shader);
-        gl_attach_shader(program, vertex_shader);
-
-        /*
-        if !gl_get_shader_compile_status(vertex_shader) {
-            return Err(gl_get_shader_info_log(vertex_shader));
-        }*/
-
-        let fragment_shader = gl_create_fragment_shader();
-        gl_shader_source(fragment_shader, fragment_src);
-        gl_compile_shader(fragment_shader);
-        gl_attach_shader(program, fragment_shader);
-
-        /*
-        if !gl_get_shader_compile_status(fragment_shader) {
-            return Err(gl_get_shader_info_log(fragment_shader));
-        }*/
-
-        let geometry_shader = if let Some(geometry_code) = geometry_src {
-            let shader = gl_create_geometry_shader();
-            gl_shader_source(shader, geometry_code);
-            gl_compile_shader(shader);
-            gl_attach_shader(program, shader);
-            Some(shader)
-        } else {
-            None
-        };
+    ) -> Result<Self, ShaderError> {
+        let mut stages = vec![
+            (ShaderStage::Vertex, vertex_src, None),
+            (ShaderStage::Fragment, fragment_src, None),
+        ];
+        if let Some(geometry_src) = geometry_src {
+            stages.push((ShaderStage::Geometry, geometry_src, None));
+        }
+        let program = link_program(&stages)?;
 
-        gl_link_program(program);
+        Ok(Self {
+            program: Cell::new(program),
+            reflection: RefCell::new(Reflection::of(program)),
+            set_uniforms: RefCell::new(HashSet::new()),
+            sources: Vec::new(),
+            modified: RefCell::new(Vec::new()),
+        })
+    }
 
-        /*
-        if !gl_get_program_link_status(program) {
-            return Err(gl_get_program_info_log(program));
-        }*/
+    /// Builds a shader whose stages may be loaded from files, which makes it reloadable with
+    /// [`Shader::reload_if_changed`] or a [`ShaderWatcher`](crate::core::ShaderWatcher).
+    pub fn from_sources(
+        vertex: ShaderSource,
+        fragment: ShaderSource,
+        geometry: Option<ShaderSource>,
+    ) -> Result<Self, ShaderError> {
+        let mut sources = vec![
+            (ShaderStage::Vertex, vertex),
+            (ShaderStage::Fragment, fragment),
+        ];
+        if let Some(geometry) = geometry {
+            sources.push((ShaderStage::Geometry, geometry));
+        }
+        let modified = sources.iter().map(|(_, src)| src.modified()).collect();
+        let program = build_from_sources(&sources)?;
 
-        // Delete shader objects after linking - they're no longer needed
-        gl_delete_shader(vertex_shader);
-        gl_delete_shader(fragment_shader);
-        if let Some(shader) = geometry_shader {
-            gl_delete_shader(shader);
+        Ok(Self {
+            program: Cell::new(program),
+            reflection: RefCell::new(Reflection::of(program)),
+            set_uniforms: RefCell::new(HashSet::new()),
+            sources,
+            modified: RefCell::new(modified),
+        })
+    }
+
+    /// Recompiles the shader if any of its source files was modified since it was last read.
+    ///
+    /// Returns whether the program was replaced. On error the previous program is kept, and
+    /// the files aren't retried until they change again.
+    pub fn reload_if_changed(&self) -> Result<bool, ShaderError> {
+        let modified: Vec<_> = self.sources.iter().map(|(_, src)| src.modified()).collect();
+        if modified == *self.modified.borrow() {
+            return Ok(false);
         }
+        *self.modified.borrow_mut() = modified;
+        self.reload().map(|()| true)
+    }
 
-        Ok(Self { program })
+    /// Recompiles the shader from its sources, keeping the previous program on error.
+    pub fn reload(&self) -> Result<(), ShaderError> {
+        if self.sources.is_empty() {
+            return Ok(());
+        }
+        let program = build_from_sources(&self.sources)?;
+        let old = self.program.replace(program);
+        *self.reflection.borrow_mut() = Reflection::of(program);
+        self.set_uniforms.borrow_mut().clear();
+        render_state::forget_program(old);
+        gl_delete_program(old);
+        Ok(())
     }
 
     pub fn use_program(&self) {
-        gl_use_program(self.program)
+        render_state::use_program(self.program.get())
     }
 
     pub fn program(&self) -> GLuint {
-        self.program
+        self.program.get()
+    }
+
+    /// Returns the location of the uniform `name`, or `None` if the program has no such
+    /// active uniform. Array elements are addressed as `name[i]`.
+    pub fn uniform_location(&self, name: &str) -> Option<GLint> {
+        self.reflection
+            .borrow()
+            .uniform_locations
+            .get(name)
+            .copied()
+    }
+
+    /// Sets the uniform `name` of this program, which must be in use (see
+    /// [`Shader::use_program`]).
+    ///
+    /// Names that aren't active uniforms are ignored, like OpenGL does for location `-1`, so
+    /// a uniform the compiler optimized away doesn't need special casing.
+    pub fn set_uniform(&self, name: &str, value: impl UniformValue) {
+        if let Some(location) = self.uniform_location(name) {
+            value.apply(location);
+            if cfg!(debug_assertions) {
+                self.set_uniforms.borrow_mut().insert(location);
+            }
+        }
+    }
+
+    /// Returns the vertex attributes the program reads, ordered by location.
+    pub fn active_attributes(&self) -> Vec<ShaderVariable> {
+        self.reflection.borrow().attributes.clone()
+    }
+
+    /// Returns the uniforms of the program, ordered by location.
+    pub fn active_uniforms(&self) -> Vec<ShaderVariable> {
+        self.reflection.borrow().uniforms.clone()
+    }
+
+    /// Checks that `geometry` provides the attributes this program reads, with matching
+    /// types, and that its uniforms have been set through [`Shader::set_uniform`].
+    ///
+    /// Uniforms are only tracked in debug builds; in release builds they are not checked.
+    pub fn validate(&self, geometry: &Geometry) -> Vec<ValidationIssue> {
+        let set_uniforms = self.set_uniforms.borrow();
+        self.reflection
+            .borrow()
+            .validate(geometry.attributes(), |location| {
+                !cfg!(debug_assertions) || set_uniforms.contains(&location)
+            })
+    }
+}
+
+fn build_from_sources(sources: &[(ShaderStage, ShaderSource)]) -> Result<GLuint, ShaderError> {
+    let loaded = sources
+        .iter()
+        .map(|(stage, src)| src.load(*stage))
+        .collect::<Result<Vec<_>, _>>()?;
+    let stages: Vec<_> = sources
+        .iter()
+        .zip(&loaded)
+        .map(|((stage, src), code)| (*stage, code.as_str(), src.path()))
+        .collect();
+    link_program(&stages)
+}
+
+/// Compiles and links the given stages, each with the file it was read from, if any.
+fn link_program(stages: &[(ShaderStage, &str, Option<&Path>)]) -> Result<GLuint, ShaderError> {
+    let mut shaders = Vec::with_capacity(stages.len());
+    for &(stage, src, path) in stages {
+        match compile_stage(stage, src) {
+            Ok(shader) => shaders.push(shader),
+            Err(mut err) => {
+                shaders.into_iter().for_each(gl_delete_shader);
+                err.path = path.map(Path::to_path_buf);
+                return Err(err);
+            }
+        }
+    }
+
+    let program = gl_create_program();
+    for &shader in &shaders {
+        gl_attach_shader(program, shader);
+    }
+    gl_link_program(program);
+
+    // Delete shader objects after linking - they're no longer needed
+    shaders.into_iter().for_each(gl_delete_shader);
+
+    if !gl_get_program_link_status(program) {
+        let log = gl_get_program_info_log(program);
+        gl_delete_program(program);
+        return Err(ShaderError::new(ShaderStage::Link, log, None));
+    }
+    Ok(program)
+}
+
+fn compile_stage(stage: ShaderStage, src: &str) -> Result<GLuint, ShaderError> {
+    let shader = match stage {
+        ShaderStage::Vertex => gl_create_vertex_shader(),
+        ShaderStage::Fragment => gl_create_fragment_shader(),
+        ShaderStage::Geometry => gl_create_geometry_shader(),
+        ShaderStage::Link => unreachable!("link is not a shader stage"),
+    };
+    gl_shader_source(shader, src);
+    gl_compile_shader(shader);
+
+    if !gl_get_shader_compile_status(shader) {
+        let log = gl_get_shader_info_log(shader);
+        gl_delete_shader(shader);
+        return Err(ShaderError::new(stage, log, Some(src)));
+    }
+    Ok(shader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_error_location;
+
+    #[test]
+    fn parses_mesa_log() {
+        let log = "0:12(5): error: `foo' undeclared\n";
+        assert_eq!(parse_error_location(log), Some((0, 12)));
+    }
+
+    #[test]
+    fn parses_nvidia_log() {
+        let log = "0(7) : error C1008: undefined variable \"foo\"\n";
+        assert_eq!(parse_error_location(log), Some((0, 7)));
+    }
+
+    #[test]
+    fn parses_prefixed_log() {
+        let log = "ERROR: 1:3: 'foo' : undeclared identifier\nERROR: 1 compilation errors.\n";
+        assert_eq!(parse_error_location(log), Some((1, 3)));
+    }
+
+    #[test]
+    fn skips_leading_warnings() {
+        let mesa =
+            "0:2(10): warning: extension `GL_foo' unsupported\n0:9(1): error: syntax error\n";
+        assert_eq!(parse_error_location(mesa), Some((0, 9)));
+
+        let nvidia =
+            "0(4) : warning C7555: 'varying' is deprecated\n0(11) : error C0000: syntax error\n";
+        assert_eq!(parse_error_location(nvidia), Some((0, 11)));
+
+        let prefixed = "WARNING: 0:1: extension not supported\nERROR: 0:6: syntax error\n";
+        assert_eq!(parse_error_location(prefixed), Some((0, 6)));
+    }
+
+    #[test]
+    fn falls_back_to_warnings() {
+        let log = "0:2(10): warning: extension `GL_foo' unsupported\nerror: linking failed\n";
+        assert_eq!(parse_error_location(log), Some((0, 2)));
+        assert_eq!(parse_error_location("error: linking failed"), None);
     }
 }
@@ -0,0 +1,71 @@
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+use crate::core::shader::{Shader, ShaderError};
+
+/// How often [`ShaderWatcher::poll`] checks the files by default.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Recompiles shaders built with [`Shader::from_sources`] when their files change on disk.
+///
+/// Call [`ShaderWatcher::poll`] once per frame, e.g. from [`App::on_update`]. A shader whose
+/// new source fails to compile keeps its previous program, and the error is returned so it
+/// can be reported.
+///
+/// [`App::on_update`]: crate::core::App::on_update
+pub struct ShaderWatcher {
+    shaders: Vec<Weak<Shader>>,
+    interval: Duration,
+    last_poll: Option<Instant>,
+}
+
+impl ShaderWatcher {
+    pub fn new() -> Self {
+        Self::with_interval(DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a watcher that checks the files at most once every `interval`.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            shaders: Vec::new(),
+            interval,
+            last_poll: None,
+        }
+    }
+
+    /// Starts watching `shader`. The watcher doesn't keep it alive.
+    pub fn watch(&mut self, shader: &Rc<Shader>) {
+        self.shaders.push(Rc::downgrade(shader));
+    }
+
+    /// Reloads the watched shaders whose files changed and returns the errors of those that
+    /// failed to compile.
+    pub fn poll(&mut self) -> Vec<ShaderError> {
+        let now = Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|last| now.duration_since(last) < self.interval)
+        {
+            return Vec::new();
+        }
+        self.last_poll = Some(now);
+
+        let mut errors = Vec::new();
+        self.shaders.retain(|shader| match shader.upgrade() {
+            Some(shader) => {
+                if let Err(err) = shader.reload_if_changed() {
+                    errors.push(err);
+                }
+                true
+            }
+            None => false,
+        });
+        errors
+    }
+}
+
+impl Default for ShaderWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,79 @@
+#version 330 core
+
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D u_texture;                  // output of the previous pass
+
+#ifdef FILL
+uniform vec4 u_color;                         // constant output, e.g. a blend factor
+#endif
+
+#ifdef BLUR
+#define MAX_BLUR_TAPS 32
+uniform vec2 u_direction;                     // one texel along the blurred axis
+uniform float u_sigma;                        // standard deviation, in pixels
+#endif
+
+#ifdef BRIGHT_PASS
+uniform float u_threshold;                    // brightness below which nothing glows
+#endif
+
+#ifdef BLOOM_COMPOSITE
+uniform sampler2D u_bloom;                    // blurred bright pass
+uniform float u_intensity;
+#endif
+
+#ifdef COLOR_GRADE
+uniform float u_brightness;
+uniform float u_contrast;
+uniform float u_saturation;
+#endif
+
+#ifdef VIGNETTE
+uniform float u_strength;
+uniform float u_radius;                       // distance from the center where darkening starts
+uniform float u_softness;
+#endif
+
+void main() {
+#ifdef FILL
+    FragColor = u_color;
+#elif defined(BLUR)
+    // separable gaussian, run once per axis
+    // clamped so that the kernel keeps covering 3 sigmas, and stays gaussian
+    float sigma = clamp(u_sigma, 0.0001, float(MAX_BLUR_TAPS) / 3.0);
+    int taps = int(ceil(sigma * 3.0));
+    vec4 sum = texture(u_texture, TexCoord);
+    float total = 1.0;
+    for (int i = 1; i <= taps; ++i) {
+        float w = exp(-0.5 * float(i * i) / (sigma * sigma));
+        sum += w * texture(u_texture, TexCoord + float(i) * u_direction);
+        sum += w * texture(u_texture, TexCoord - float(i) * u_direction);
+        total += 2.0 * w;
+    }
+    FragColor = sum / total;
+#else
+    FragColor = texture(u_texture, TexCoord);
+#endif
+
+#ifdef BRIGHT_PASS
+    float brightness = max(FragColor.r, max(FragColor.g, FragColor.b));
+    FragColor.rgb *= max(brightness - u_threshold, 0.0) / max(brightness, 0.0001);
+#endif
+
+#ifdef BLOOM_COMPOSITE
+    FragColor.rgb += u_intensity * texture(u_bloom, TexCoord).rgb;
+#endif
+
+#ifdef COLOR_GRADE
+    vec3 graded = (FragColor.rgb - 0.5) * u_contrast + 0.5 + u_brightness;
+    float luma = dot(graded, vec3(0.2126, 0.7152, 0.0722));
+    FragColor.rgb = mix(vec3(luma), graded, u_saturation);
+#endif
+
+#ifdef VIGNETTE
+    float d = distance(TexCoord, vec2(0.5));
+    FragColor.rgb *= 1.0 - u_strength * smoothstep(u_radius, u_radius + u_softness, d);
+#endif
+}
@@ -1,10 +1,324 @@
 use std::ffi::c_void;
 use crate::core::image::{Image};
-use crate::core::engine::opengl::{gl_bind_texture, gl_gen_texture, gl_generate_mipmap, gl_tex_image_2d, gl_tex_parameteri, GL_LINEAR, GL_LINEAR_MIPMAP_LINEAR, GL_REPEAT, GL_RGBA, GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, GL_TEXTURE_MIN_FILTER, GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T, GL_UNSIGNED_BYTE};
+use crate::core::render_state;
+use crate::core::engine::opengl::{
+    GL_CLAMP_TO_EDGE, GL_FLOAT, GL_LINEAR, GL_LINEAR_MIPMAP_LINEAR, GL_MIRRORED_REPEAT,
+    GL_NEAREST, GL_NEAREST_MIPMAP_LINEAR, GL_R8, GL_R32F, GL_RED, GL_RGBA16F, GL_REPEAT, GL_RG, GL_RG8,
+    GL_RGBA, GL_RGBA8, GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, GL_TEXTURE_MIN_FILTER,
+    GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T, GL_UNPACK_ALIGNMENT, GL_UNSIGNED_BYTE, GLenum, GLint,
+    GLuint, gl_delete_texture, gl_gen_texture,
+    gl_generate_mipmap, gl_pixel_storei, gl_tex_image_2d, gl_tex_parameteri, gl_tex_sub_image_2d,
+};
 
+/// The pixel format of a [`Texture`], both in memory and on the GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureFormat {
+    /// One byte per pixel, read as `.r` in shaders, e.g. for masks or intensity rasters.
+    R8,
+    /// Two bytes per pixel, read as `.rg`.
+    RG8,
+    /// Four bytes per pixel.
+    #[default]
+    RGBA8,
+    /// One `f32` per pixel, e.g. for data fields sampled by a color map shader.
+    R32F,
+    /// Four half floats per pixel, e.g. for accumulation buffers that must not quantize
+    /// small values. Uploaded from `f32`s.
+    RGBA16F,
+}
+
+impl TextureFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            TextureFormat::R8 => 1,
+            TextureFormat::RG8 => 2,
+            TextureFormat::RGBA8 | TextureFormat::R32F => 4,
+            TextureFormat::RGBA16F => 16,
+        }
+    }
+
+    pub(crate) fn internal_format(self) -> GLint {
+        match self {
+            TextureFormat::R8 => GL_R8,
+            TextureFormat::RG8 => GL_RG8,
+            TextureFormat::RGBA8 => GL_RGBA8,
+            TextureFormat::R32F => GL_R32F,
+            TextureFormat::RGBA16F => GL_RGBA16F,
+        }
+    }
+
+    fn format(self) -> GLenum {
+        match self {
+            TextureFormat::R8 | TextureFormat::R32F => GL_RED,
+            TextureFormat::RG8 => GL_RG,
+            TextureFormat::RGBA8 | TextureFormat::RGBA16F => GL_RGBA as GLenum,
+        }
+    }
+
+    fn data_type(self) -> GLenum {
+        match self {
+            TextureFormat::R32F | TextureFormat::RGBA16F => GL_FLOAT,
+            _ => GL_UNSIGNED_BYTE,
+        }
+    }
+}
+
+/// What a [`Texture`] samples outside of `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureWrap {
+    #[default]
+    Repeat,
+    MirroredRepeat,
+    ClampToEdge,
+}
+
+impl TextureWrap {
+    fn gl_param(self) -> GLint {
+        match self {
+            TextureWrap::Repeat => GL_REPEAT,
+            TextureWrap::MirroredRepeat => GL_MIRRORED_REPEAT,
+            TextureWrap::ClampToEdge => GL_CLAMP_TO_EDGE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureFilter {
+    /// Blocky magnification, for pixel-exact rasters.
+    Nearest,
+    #[default]
+    Linear,
+}
+
+/// How a [`Texture`] is stored and sampled. The default matches what
+/// [`generate_texture_from_image`] always did: RGBA8, repeat, trilinear filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureOptions {
+    pub format: TextureFormat,
+    pub wrap: TextureWrap,
+    pub min_filter: TextureFilter,
+    pub mag_filter: TextureFilter,
+    /// Whether mipmaps are generated, and regenerated after each update.
+    pub mipmaps: bool,
+}
+
+impl Default for TextureOptions {
+    fn default() -> Self {
+        Self {
+            format: TextureFormat::RGBA8,
+            wrap: TextureWrap::Repeat,
+            min_filter: TextureFilter::Linear,
+            mag_filter: TextureFilter::Linear,
+            mipmaps: true,
+        }
+    }
+}
+
+impl TextureOptions {
+    pub fn format(mut self, format: TextureFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextureWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets both the minification and the magnification filter.
+    pub fn filter(self, filter: TextureFilter) -> Self {
+        self.min_filter(filter).mag_filter(filter)
+    }
+
+    pub fn min_filter(mut self, filter: TextureFilter) -> Self {
+        self.min_filter = filter;
+        self
+    }
+
+    pub fn mag_filter(mut self, filter: TextureFilter) -> Self {
+        self.mag_filter = filter;
+        self
+    }
+
+    pub fn mipmaps(mut self, enabled: bool) -> Self {
+        self.mipmaps = enabled;
+        self
+    }
+}
+
+/// A 2D texture owned by the GPU, deleted when dropped.
+///
+/// Share it between meshes with an `Rc`, see [`Mesh::with_texture`].
+///
+/// [`Mesh::with_texture`]: crate::core::Mesh::with_texture
+#[derive(Debug)]
+pub struct Texture {
+    id: GLuint,
+    width: u32,
+    height: u32,
+    options: TextureOptions,
+}
+
+impl Texture {
+    /// Creates a texture with uninitialized contents, to be filled with
+    /// [`Texture::update_region`].
+    pub fn empty(width: u32, height: u32, options: TextureOptions) -> Self {
+        Self::create(width, height, std::ptr::null(), options)
+    }
+
+    /// Creates a texture from tightly packed rows of pixels in `options.format`, the first
+    /// row being at texture coordinate `t = 0`. `T` is `u8` for the byte formats and `f32`
+    /// for the float ones.
+    pub fn from_data<T: Copy>(width: u32, height: u32, data: &[T], options: TextureOptions) -> Self {
+        assert_eq!(
+            std::mem::size_of_val(data),
+            width as usize * height as usize * options.format.bytes_per_pixel(),
+            "texture data size doesn't match {width}x{height} {:?}",
+            options.format
+        );
+        Self::create(width, height, data.as_ptr() as *const c_void, options)
+    }
+
+    /// Creates an RGBA8 texture from `image`, ignoring `options.format`.
+    pub fn from_image(image: &Image, options: TextureOptions) -> Self {
+        let options = options.format(TextureFormat::RGBA8);
+        Self::from_data(image.width, image.height, &image.pixels, options)
+    }
+
+    fn create(width: u32, height: u32, data: *const c_void, options: TextureOptions) -> Self {
+        let id = gl_gen_texture();
+        render_state::bind_texture(0, id);
+
+        gl_tex_parameteri(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, options.wrap.gl_param());
+        gl_tex_parameteri(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, options.wrap.gl_param());
+        let min_filter = match (options.min_filter, options.mipmaps) {
+            (TextureFilter::Nearest, false) => GL_NEAREST,
+            (TextureFilter::Linear, false) => GL_LINEAR,
+            (TextureFilter::Nearest, true) => GL_NEAREST_MIPMAP_LINEAR,
+            (TextureFilter::Linear, true) => GL_LINEAR_MIPMAP_LINEAR,
+        };
+        gl_tex_parameteri(GL_TEXTURE_2D, GL_TEXTURE_MIN_FILTER, min_filter);
+        let mag_filter = match options.mag_filter {
+            TextureFilter::Nearest => GL_NEAREST,
+            TextureFilter::Linear => GL_LINEAR,
+        };
+        gl_tex_parameteri(GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, mag_filter);
+
+        let format = options.format;
+        with_unpack_alignment(format, || {
+            gl_tex_image_2d(
+                GL_TEXTURE_2D,
+                0,
+                format.internal_format(),
+                width as i32,
+                height as i32,
+                0,
+                format.format(),
+                format.data_type(),
+                data,
+            )
+        });
+        if options.mipmaps && !data.is_null() {
+            gl_generate_mipmap(GL_TEXTURE_2D);
+        }
+
+        Self {
+            id,
+            width,
+            height,
+            options,
+        }
+    }
+
+    /// Overwrites the `width` x `height` pixels at `(x, y)` with `data`, laid out as in
+    /// [`Texture::from_data`]. Mipmaps, if enabled, are regenerated.
+    pub fn update_region<T: Copy>(&self, x: u32, y: u32, width: u32, height: u32, data: &[T]) {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "region {width}x{height} at ({x}, {y}) is outside the {}x{} texture",
+            self.width,
+            self.height
+        );
+        let format = self.options.format;
+        assert_eq!(
+            std::mem::size_of_val(data),
+            width as usize * height as usize * format.bytes_per_pixel(),
+            "texture data size doesn't match {width}x{height} {format:?}"
+        );
+
+        render_state::bind_texture(0, self.id);
+        with_unpack_alignment(format, || {
+            gl_tex_sub_image_2d(
+                GL_TEXTURE_2D,
+                0,
+                x as i32,
+                y as i32,
+                width as i32,
+                height as i32,
+                format.format(),
+                format.data_type(),
+                data.as_ptr() as *const c_void,
+            )
+        });
+        if self.options.mipmaps {
+            gl_generate_mipmap(GL_TEXTURE_2D);
+        }
+    }
+
+    /// Regenerates the mipmaps, if enabled, after the contents were changed on the GPU.
+    pub(crate) fn update_mipmaps(&self) {
+        if self.options.mipmaps {
+            render_state::bind_texture(0, self.id);
+            gl_generate_mipmap(GL_TEXTURE_2D);
+        }
+    }
+
+    /// Binds the texture to texture unit `unit`.
+    pub fn bind(&self, unit: u32) {
+        render_state::bind_texture(unit, self.id);
+    }
+
+    pub fn id(&self) -> GLuint {
+        self.id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn options(&self) -> TextureOptions {
+        self.options
+    }
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        render_state::forget_texture(self.id);
+        gl_delete_texture(self.id);
+    }
+}
+
+/// Runs `upload` with rows aligned to single bytes when `format` rows may not be a multiple
+/// of 4 bytes long.
+fn with_unpack_alignment(format: TextureFormat, upload: impl FnOnce()) {
+    let byte_aligned = format.bytes_per_pixel() < 4;
+    if byte_aligned {
+        gl_pixel_storei(GL_UNPACK_ALIGNMENT, 1);
+    }
+    upload();
+    if byte_aligned {
+        gl_pixel_storei(GL_UNPACK_ALIGNMENT, 4);
+    }
+}
+
+#[deprecated(note = "use `Texture::from_image`, which deletes the texture when dropped")]
 pub fn generate_texture_from_image(image: &Image) -> u32 {
     let texture = gl_gen_texture();
-    gl_bind_texture(GL_TEXTURE_2D, texture);
+    render_state::bind_texture(0, texture);
 
     gl_tex_parameteri(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_REPEAT);
     gl_tex_parameteri(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_REPEAT);
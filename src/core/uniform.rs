@@ -0,0 +1,77 @@
+use glam::{Mat3, Mat4, Vec2, Vec3, Vec4};
+
+use crate::core::color::Color;
+use crate::core::engine::opengl::{
+    GLboolean, GLint, gl_uniform_1f, gl_uniform_1i, gl_uniform_2f, gl_uniform_3f, gl_uniform_4f,
+    gl_uniform_matrix_3fv, gl_uniform_matrix_4fv,
+};
+
+/// A value that can be uploaded to a shader uniform, see [`Shader::set_uniform`].
+///
+/// [`Shader::set_uniform`]: crate::core::Shader::set_uniform
+pub trait UniformValue {
+    /// Uploads the value to `location` of the program currently in use.
+    fn apply(&self, location: GLint);
+}
+
+impl<T: UniformValue + ?Sized> UniformValue for &T {
+    fn apply(&self, location: GLint) {
+        (**self).apply(location)
+    }
+}
+
+impl UniformValue for f32 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_1f(location, *self);
+    }
+}
+
+/// Also used for `bool` and sampler uniforms.
+impl UniformValue for i32 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_1i(location, *self);
+    }
+}
+
+impl UniformValue for Vec2 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_2f(location, self.x, self.y);
+    }
+}
+
+impl UniformValue for Vec3 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_3f(location, self.x, self.y, self.z);
+    }
+}
+
+impl UniformValue for Vec4 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_4f(location, self.x, self.y, self.z, self.w);
+    }
+}
+
+impl UniformValue for Mat3 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_matrix_3fv(location, 1, GLboolean::FALSE, self.to_cols_array().as_ptr());
+    }
+}
+
+impl UniformValue for Mat4 {
+    fn apply(&self, location: GLint) {
+        gl_uniform_matrix_4fv(location, 1, GLboolean::FALSE, self.to_cols_array().as_ptr());
+    }
+}
+
+/// Uploaded as a `vec4` (rgba).
+impl UniformValue for Color {
+    fn apply(&self, location: GLint) {
+        gl_uniform_4f(
+            location,
+            self.red_value(),
+            self.green_value(),
+            self.blue_value(),
+            self.alpha(),
+        );
+    }
+}
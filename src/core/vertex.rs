@@ -0,0 +1,140 @@
+use crate::core::engine::opengl::{
+    GL_BYTE, GL_FLOAT, GL_INT, GL_SHORT, GL_UNSIGNED_BYTE, GL_UNSIGNED_INT, GL_UNSIGNED_SHORT,
+    GLenum, GLint, Vec2,
+};
+use crate::core::geometry::Attribute;
+
+/// A vertex (or instance) struct whose fields map to shader attributes, see
+/// [`Geometry::from_vertices`] and [`Geometry::enable_instancing`].
+///
+/// Implement it with [`impl_vertex!`](crate::impl_vertex), which computes the offsets and
+/// component types from the struct definition.
+///
+/// [`Geometry::from_vertices`]: crate::core::Geometry::from_vertices
+/// [`Geometry::enable_instancing`]: crate::core::Geometry::enable_instancing
+pub trait Vertex: Copy {
+    /// One attribute per field, with the stride and offsets of the struct.
+    fn attributes() -> Vec<Attribute>;
+}
+
+/// The type of a [`Vertex`] field: a scalar, an array of up to 4 scalars, or a vector.
+pub trait VertexComponent: Copy {
+    /// The OpenGL type of each component, e.g. `GL_FLOAT`.
+    const DATA_TYPE: GLenum;
+    /// Number of components, 1 to 4.
+    const SIZE: GLint;
+    /// Whether the components are integers, which are passed as such to `int`/`uint` shader
+    /// inputs unless the attribute is [normalized](Attribute::normalized).
+    const INTEGER: bool;
+}
+
+macro_rules! impl_scalar_component {
+    ($($ty:ty => $gl_type:expr, $integer:expr;)*) => {
+        $(
+            impl VertexComponent for $ty {
+                const DATA_TYPE: GLenum = $gl_type;
+                const SIZE: GLint = 1;
+                const INTEGER: bool = $integer;
+            }
+
+            impl<const N: usize> VertexComponent for [$ty; N] {
+                const DATA_TYPE: GLenum = $gl_type;
+                const SIZE: GLint = {
+                    assert!(N >= 1 && N <= 4, "vertex attributes have 1 to 4 components");
+                    N as GLint
+                };
+                const INTEGER: bool = $integer;
+            }
+        )*
+    };
+}
+
+impl_scalar_component! {
+    f32 => GL_FLOAT, false;
+    i8 => GL_BYTE, true;
+    u8 => GL_UNSIGNED_BYTE, true;
+    i16 => GL_SHORT, true;
+    u16 => GL_UNSIGNED_SHORT, true;
+    i32 => GL_INT, true;
+    u32 => GL_UNSIGNED_INT, true;
+}
+
+macro_rules! impl_vector_component {
+    ($($ty:ty => $size:expr;)*) => {
+        $(
+            impl VertexComponent for $ty {
+                const DATA_TYPE: GLenum = GL_FLOAT;
+                const SIZE: GLint = $size;
+                const INTEGER: bool = false;
+            }
+        )*
+    };
+}
+
+impl_vector_component! {
+    Vec2 => 2;
+    glam::Vec2 => 2;
+    glam::Vec3 => 3;
+    glam::Vec4 => 4;
+}
+
+/// Builds the attribute of a field of type `C`, which is inferred from `_field`. Used by
+/// [`impl_vertex!`](crate::impl_vertex).
+#[doc(hidden)]
+pub fn vertex_field_attribute<V, C: VertexComponent>(
+    _field: fn(&V) -> &C,
+    location: u32,
+    offset: usize,
+) -> Attribute {
+    let attribute = Attribute::from_bytes(
+        location,
+        C::SIZE,
+        C::DATA_TYPE,
+        std::mem::size_of::<V>(),
+        offset,
+    );
+    if C::INTEGER {
+        attribute.integer()
+    } else {
+        attribute
+    }
+}
+
+/// Implements [`Vertex`](crate::core::Vertex) for a struct by listing its fields with their
+/// attribute locations.
+///
+/// Integer fields are passed to the shader as integers; follow the location with
+/// `normalized` to read them as floats in `[0, 1]` instead (e.g. 4-byte colors), or with
+/// `per_instance` for instance data. See the [`Attribute`](crate::core::Attribute) methods.
+///
+/// ```ignore
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct PickVertex {
+///     position: [f32; 2],
+///     color: [u8; 4],
+///     id: u32,
+/// }
+///
+/// impl_vertex!(PickVertex {
+///     position => 0,
+///     color => 1 normalized,
+///     id => 2,
+/// });
+/// ```
+#[macro_export]
+macro_rules! impl_vertex {
+    ($ty:ty { $($field:ident => $location:literal $($modifier:ident)*),* $(,)? }) => {
+        impl $crate::core::Vertex for $ty {
+            fn attributes() -> Vec<$crate::core::Attribute> {
+                vec![$(
+                    $crate::core::vertex_field_attribute(
+                        |v: &$ty| &v.$field,
+                        $location,
+                        ::std::mem::offset_of!($ty, $field),
+                    )$(.$modifier())*
+                ),*]
+            }
+        }
+    };
+}
@@ -1,15 +1,29 @@
-use std::cell::Cell;
-use std::ffi::c_void;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashSet, VecDeque};
+use std::ffi::{CStr, c_char, c_void};
+use std::path::PathBuf;
 use std::rc::Rc;
 
-use crate::core::engine::opengl::{gl_clear_color, gl_viewport};
-use crate::core::engine::glfw::{GLFWwindow, glfw_create_window, glfw_destroy_window, glfw_get_window_content_scale, glfw_get_window_user_pointer, glfw_poll_events, glfw_set_cursor_pos_callback, glfw_set_scroll_callback, glfw_set_window_size_callback, glfw_set_window_user_pointer, glfw_swap_buffers, glfw_window_should_close};
+use crate::core::engine::opengl::{GL_COLOR_BUFFER_BIT, gl_clear, gl_clear_color};
+use crate::core::engine::glfw::{GLFWwindow, WindowConfig, glfw_create_window_with_config, glfw_destroy_window, glfw_get_window_content_scale, glfw_get_window_size, glfw_get_window_user_pointer, glfw_poll_events, glfw_set_char_callback, glfw_set_cursor_pos_callback, glfw_set_drop_callback, glfw_set_key_callback, glfw_set_mouse_button_callback, glfw_set_scroll_callback, glfw_set_window_close_callback, glfw_set_window_focus_callback, glfw_set_window_size_callback, glfw_set_window_user_pointer, glfw_swap_buffers, glfw_swap_interval, glfw_wait_events, glfw_wait_events_timeout, glfw_window_should_close};
+use crate::core::event::Event;
+use crate::core::render_state;
+use crate::core::input::{Action, DragEvent, DragTracker, Key, Modifiers, MouseButton};
 
+/// Upper bound on undrained events, the oldest events are dropped beyond it.
+const MAX_QUEUED_EVENTS: usize = 1024;
+
+
+type KeyCallback = Box<dyn FnMut(Key, i32, Action, Modifiers)>;
+type MouseButtonCallback = Box<dyn FnMut(MouseButton, Action, Modifiers)>;
 
 /// Shared inner state that both Window and WindowHandle can access.
 struct InnerWindow {
     width: Cell<i32>,
     height: Cell<i32>,
+    keys_down: RefCell<HashSet<Key>>,
+    buttons_down: RefCell<HashSet<MouseButton>>,
+    cursor_position: Cell<(f64, f64)>,
 }
 
 pub struct Window {
@@ -18,6 +32,115 @@ pub struct Window {
     on_resize: Option<Box<dyn FnMut(i32, i32)>>,
     on_scroll: Option<Box<dyn FnMut(f64, f64)>>,
     on_cursor_position: Option<Box<dyn FnMut(f64, f64)>>,
+    on_key: Option<KeyCallback>,
+    on_char: Option<Box<dyn FnMut(char)>>,
+    on_mouse_button: Option<MouseButtonCallback>,
+    on_drag: Option<Box<dyn FnMut(DragEvent)>>,
+    drag_tracker: DragTracker,
+    events: VecDeque<Event>,
+}
+
+/// Configures and creates a [`Window`].
+///
+/// Defaults match [`Window::new`]: 4x MSAA, an OpenGL 3.3 core context, a resizable,
+/// decorated and visible window, and the driver's default swap interval.
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    title: String,
+    width: i32,
+    height: i32,
+    config: WindowConfig,
+}
+
+impl WindowBuilder {
+    pub fn new(title: &str, width: i32, height: i32) -> Self {
+        Self {
+            title: title.to_string(),
+            width,
+            height,
+            config: WindowConfig::default(),
+        }
+    }
+
+    /// Number of MSAA samples per pixel, `0` disables multisampling.
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.config.samples = samples;
+        self
+    }
+
+    /// Enables or disables vsync (a swap interval of 1 or 0).
+    pub fn vsync(self, enabled: bool) -> Self {
+        self.swap_interval(if enabled { 1 } else { 0 })
+    }
+
+    /// Number of vertical refreshes to wait for between buffer swaps.
+    pub fn swap_interval(mut self, interval: i32) -> Self {
+        self.config.swap_interval = interval;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.config.resizable = resizable as i32;
+        self
+    }
+
+    pub fn decorated(mut self, decorated: bool) -> Self {
+        self.config.decorated = decorated as i32;
+        self
+    }
+
+    /// Creates the window hidden when `false`, e.g. for offscreen rendering.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.config.visible = visible as i32;
+        self
+    }
+
+    /// Initial position of the window's content area, in screen coordinates.
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.config.has_position = 1;
+        self.config.pos_x = x;
+        self.config.pos_y = y;
+        self
+    }
+
+    /// Creates a fullscreen window on the monitor at `monitor` (`0` is the primary monitor).
+    /// A width or height of `0` uses the monitor's current video mode. Falls back to a
+    /// windowed window if the monitor doesn't exist.
+    pub fn fullscreen(mut self, monitor: usize) -> Self {
+        self.config.monitor = monitor as i32;
+        self
+    }
+
+    /// Creates the window on GLFW's null platform with an OSMesa (Mesa llvmpipe) context,
+    /// rendering into an offscreen buffer. This works on build servers without a display or
+    /// GPU, but requires `libOSMesa` at runtime. Headless windows are hidden and don't use
+    /// multisampling.
+    ///
+    /// GLFW selects its platform once per initialization, so headless and on-screen windows
+    /// can't be mixed in the same process.
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.config.headless = headless as i32;
+        if headless {
+            self.config.visible = 0;
+            self.config.samples = 0;
+        }
+        self
+    }
+
+    /// Requested OpenGL core profile version, at least 3.3.
+    pub fn gl_version(mut self, major: i32, minor: i32) -> Self {
+        self.config.context_version_major = major;
+        self.config.context_version_minor = minor;
+        self
+    }
+
+    /// Creates the window and makes its OpenGL context current.
+    ///
+    /// # Panics
+    /// Panics if GLFW fails to create the window or its context.
+    pub fn build(self) -> Box<Window> {
+        Window::create(&self.title, self.width, self.height, &self.config)
+    }
 }
 
 /// Cheap, cloneable handle to query window state without owning the window.
@@ -27,7 +150,7 @@ pub struct WindowHandle {
 }
 
 extern "C" fn _on_viewport_resized(_window: *const GLFWwindow, width: i32, height: i32) {
-    gl_viewport(0, 0, width, height);
+    render_state::set_viewport([0, 0, width, height]);
 }
 
 extern "C" fn _on_window_resized_callback(_window: *const GLFWwindow, width: i32, height: i32){
@@ -62,18 +185,116 @@ extern "C" fn _on_cursor_position_callback(_window: *const GLFWwindow, x_pos: f6
     }
 }
 
+extern "C" fn _on_mouse_button_callback(_window: *const GLFWwindow, button: i32, action: i32, mods: i32) {
+    let user_ptr = glfw_get_window_user_pointer(_window);
+    if !user_ptr.is_null() {
+        unsafe {
+            let window_ref: &mut Window = &mut *(user_ptr as *mut Window);
+            if let Some(button) = MouseButton::from_glfw(button) {
+                window_ref._on_mouse_button(button, Action::from_glfw(action), Modifiers::from_glfw(mods));
+            }
+        }
+    }
+}
+
+extern "C" fn _on_window_focus_callback(_window: *const GLFWwindow, focused: i32) {
+    let user_ptr = glfw_get_window_user_pointer(_window);
+    if !user_ptr.is_null() {
+        unsafe {
+            let window_ref: &mut Window = &mut *(user_ptr as *mut Window);
+            window_ref.push_event(Event::Focus(focused != 0));
+        }
+    }
+}
+
+extern "C" fn _on_window_close_callback(_window: *const GLFWwindow) {
+    let user_ptr = glfw_get_window_user_pointer(_window);
+    if !user_ptr.is_null() {
+        unsafe {
+            let window_ref: &mut Window = &mut *(user_ptr as *mut Window);
+            window_ref.push_event(Event::CloseRequested);
+        }
+    }
+}
+
+extern "C" fn _on_drop_callback(_window: *const GLFWwindow, path_count: i32, paths: *const *const c_char) {
+    let user_ptr = glfw_get_window_user_pointer(_window);
+    if !user_ptr.is_null() {
+        unsafe {
+            let window_ref: &mut Window = &mut *(user_ptr as *mut Window);
+            let paths = (0..path_count.max(0) as usize)
+                .map(|i| PathBuf::from(CStr::from_ptr(*paths.add(i)).to_string_lossy().into_owned()))
+                .collect();
+            window_ref.push_event(Event::FileDrop(paths));
+        }
+    }
+}
+
+extern "C" fn _on_key_callback(_window: *const GLFWwindow, key: i32, scancode: i32, action: i32, mods: i32) {
+    let user_ptr = glfw_get_window_user_pointer(_window);
+    if !user_ptr.is_null() {
+        unsafe {
+            let window_ref: &mut Window = &mut *(user_ptr as *mut Window);
+            window_ref._on_key(Key::from_glfw(key), scancode, Action::from_glfw(action), Modifiers::from_glfw(mods));
+        }
+    }
+}
+
+extern "C" fn _on_char_callback(_window: *const GLFWwindow, codepoint: u32) {
+    let user_ptr = glfw_get_window_user_pointer(_window);
+    if !user_ptr.is_null() {
+        unsafe {
+            let window_ref: &mut Window = &mut *(user_ptr as *mut Window);
+            if let Some(c) = char::from_u32(codepoint) {
+                window_ref._on_char(c);
+            }
+        }
+    }
+}
+
 impl Window {
     pub fn new(title: &str, width: i32, height: i32) -> Box<Self> {
-        let glfw_window = glfw_create_window(title, width, height, Some(_on_viewport_resized));
+        WindowBuilder::new(title, width, height).build()
+    }
+
+    /// Creates an invisible window whose OpenGL context renders into an offscreen buffer,
+    /// without a display server or GPU. See [`WindowBuilder::headless`].
+    pub fn headless(width: i32, height: i32) -> Box<Self> {
+        WindowBuilder::new("headless", width, height).headless(true).build()
+    }
+
+    /// Returns a [`WindowBuilder`] to configure multisampling, vsync, fullscreen and other
+    /// creation options.
+    pub fn builder(title: &str, width: i32, height: i32) -> WindowBuilder {
+        WindowBuilder::new(title, width, height)
+    }
+
+    fn create(title: &str, width: i32, height: i32, config: &WindowConfig) -> Box<Self> {
+        let glfw_window =
+            glfw_create_window_with_config(title, width, height, config, Some(_on_viewport_resized));
+        assert!(!glfw_window.is_null(), "Failed to create window '{}'", title);
+
         // hook callbacks
         glfw_set_window_size_callback(glfw_window, Some(_on_window_resized_callback));
         glfw_set_scroll_callback(glfw_window, Some(_on_scroll_callback));
         glfw_set_cursor_pos_callback(glfw_window, Some(_on_cursor_position_callback));
+        glfw_set_mouse_button_callback(glfw_window, Some(_on_mouse_button_callback));
+        glfw_set_key_callback(glfw_window, Some(_on_key_callback));
+        glfw_set_char_callback(glfw_window, Some(_on_char_callback));
+        glfw_set_window_focus_callback(glfw_window, Some(_on_window_focus_callback));
+        glfw_set_window_close_callback(glfw_window, Some(_on_window_close_callback));
+        glfw_set_drop_callback(glfw_window, Some(_on_drop_callback));
 
+        // fullscreen windows may not get the requested size
+        let (mut width, mut height) = (0, 0);
+        glfw_get_window_size(glfw_window, &mut width, &mut height);
 
         let inner = Rc::new(InnerWindow {
             width: Cell::new(width),
             height: Cell::new(height),
+            keys_down: RefCell::new(HashSet::new()),
+            buttons_down: RefCell::new(HashSet::new()),
+            cursor_position: Cell::new((0.0, 0.0)),
         });
 
         let mut window = Box::new(Window {
@@ -82,6 +303,12 @@ impl Window {
             on_resize: None,
             on_scroll: None,
             on_cursor_position: None,
+            on_key: None,
+            on_char: None,
+            on_mouse_button: None,
+            on_drag: None,
+            drag_tracker: DragTracker::new(),
+            events: VecDeque::new(),
         });
         glfw_set_window_user_pointer(glfw_window, &mut *window as *mut _ as *mut c_void);
         window
@@ -102,23 +329,63 @@ impl Window {
         self.inner.height.get()
     }
 
+    /// Returns `true` while `key` is held down, always `false` for [`Key::Unknown`].
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.inner.keys_down.borrow().contains(&key)
+    }
+
+    /// Returns `true` while `button` is held down.
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.inner.buttons_down.borrow().contains(&button)
+    }
+
+    /// Returns the last known cursor position in window coordinates.
+    pub fn cursor_position(&self) -> (f64, f64) {
+        self.inner.cursor_position.get()
+    }
+
     pub fn content_scale(&self)->(f32, f32){
         glfw_get_window_content_scale(self.glfw_window)
     }
     
     pub fn clear_color(&self, red: f32, green: f32, blue: f32, alpha: f32) {
         gl_clear_color(red, green, blue, alpha);
+        gl_clear(GL_COLOR_BUFFER_BIT);
+    }
+    /// Sets the number of vertical refreshes to wait for between buffer swaps:
+    /// `0` disables vsync, `1` enables it.
+    pub fn set_swap_interval(&self, interval: i32) {
+        glfw_swap_interval(interval);
     }
+
     pub fn window_should_close(&self) -> bool {
         glfw_window_should_close(self.glfw_window)
     }
+    /// Shows the rendered frame, which also ends the frame counted by
+    /// [`Renderer::frame_stats`](crate::core::Renderer::frame_stats).
     pub fn swap_buffers(&self) {
         glfw_swap_buffers(self.glfw_window);
+        render_state::end_frame();
     }
     pub fn poll_events(&self) {
         glfw_poll_events();
     }
 
+    /// Blocks until at least one event arrives, then processes all pending events.
+    pub fn wait_events(&self) {
+        glfw_wait_events();
+    }
+
+    /// Like [`Window::wait_events`], but returns after `timeout` seconds at the latest.
+    pub fn wait_events_timeout(&self, timeout: f64) {
+        glfw_wait_events_timeout(timeout);
+    }
+
+    /// Returns `true` if events are waiting in the queue returned by [`Window::drain_events`].
+    pub fn has_pending_events(&self) -> bool {
+        !self.events.is_empty()
+    }
+
     pub fn on_resize<F>(&mut self, f: F)
     where
         F: FnMut(i32, i32) + 'static,
@@ -140,21 +407,142 @@ impl Window {
         self.on_cursor_position = Some(Box::new(f));
     }
 
+    /// Removes and returns the queued events, oldest first.
+    ///
+    /// Every GLFW callback also records an [`Event`], so input can be handled in one place
+    /// from the main loop instead of through per-event closures. Events are gathered by
+    /// [`Window::poll_events`]; undrained events beyond an internal limit are discarded,
+    /// oldest first.
+    pub fn drain_events(&mut self) -> impl Iterator<Item = Event> + '_ {
+        self.events.drain(..)
+    }
+
+    fn push_event(&mut self, event: Event) {
+        if self.events.len() == MAX_QUEUED_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// Registers a callback invoked on key press, repeat and release with the key,
+    /// its platform-specific scancode, the action and the active modifiers.
+    pub fn on_key<F>(&mut self, f: F)
+    where
+        F: FnMut(Key, i32, Action, Modifiers) + 'static,
+    {
+        self.on_key = Some(Box::new(f));
+    }
+
+    /// Registers a callback invoked with each Unicode character typed, after keyboard
+    /// layout and modifiers have been applied. Use this for text input.
+    pub fn on_char<F>(&mut self, f: F)
+    where
+        F: FnMut(char) + 'static,
+    {
+        self.on_char = Some(Box::new(f));
+    }
+
+    /// Registers a callback invoked when a mouse button is pressed or released. The cursor
+    /// position at the time of the event is available from [`Window::cursor_position`].
+    pub fn on_mouse_button<F>(&mut self, f: F)
+    where
+        F: FnMut(MouseButton, Action, Modifiers) + 'static,
+    {
+        self.on_mouse_button = Some(Box::new(f));
+    }
+
+    /// Registers a callback receiving drag gestures (start, move, end) derived from mouse
+    /// button and cursor events.
+    pub fn on_drag<F>(&mut self, f: F)
+    where
+        F: FnMut(DragEvent) + 'static,
+    {
+        self.on_drag = Some(Box::new(f));
+    }
+
+    /// Sets how far, in pixels, the cursor must move with a button held before a drag starts.
+    pub fn set_drag_threshold(&mut self, threshold: f64) {
+        self.drag_tracker = DragTracker::with_threshold(threshold);
+    }
+
     fn _on_resize(&mut self, width: i32, height: i32) {
+        self.push_event(Event::Resize { width, height });
         if let Some(callback) = &mut self.on_resize {
             callback(width, height);
         }
     }
 
     fn _on_scroll(&mut self, x_offset: f64, y_offset: f64) {
+        self.push_event(Event::Scroll { x_offset, y_offset });
         if let Some(callback) = &mut self.on_scroll {
             callback(x_offset, y_offset);
         }
     }
     fn _on_cursor_position(&mut self, x_pos: f64, y_pos: f64) {
+        self.inner.cursor_position.set((x_pos, y_pos));
+        self.push_event(Event::CursorMoved { x: x_pos, y: y_pos });
         if let Some(callback) = &mut self.on_cursor_position {
             callback(x_pos, y_pos);
         }
+        let mut drag_events = Vec::new();
+        self.drag_tracker.cursor_moved(x_pos, y_pos, |event| drag_events.push(event));
+        for event in drag_events {
+            self._on_drag(event);
+        }
+    }
+
+    fn _on_mouse_button(&mut self, button: MouseButton, action: Action, modifiers: Modifiers) {
+        match action {
+            Action::Press => {
+                self.inner.buttons_down.borrow_mut().insert(button);
+            }
+            Action::Release => {
+                self.inner.buttons_down.borrow_mut().remove(&button);
+            }
+            Action::Repeat => {}
+        }
+        self.push_event(Event::MouseButton { button, action, modifiers });
+        if let Some(callback) = &mut self.on_mouse_button {
+            callback(button, action, modifiers);
+        }
+        let (x, y) = self.inner.cursor_position.get();
+        let mut drag_events = Vec::new();
+        self.drag_tracker.button(button, action, x, y, |event| drag_events.push(event));
+        for event in drag_events {
+            self._on_drag(event);
+        }
+    }
+
+    fn _on_drag(&mut self, event: DragEvent) {
+        self.push_event(Event::Drag(event));
+        if let Some(callback) = &mut self.on_drag {
+            callback(event);
+        }
+    }
+
+    fn _on_key(&mut self, key: Key, scancode: i32, action: Action, modifiers: Modifiers) {
+        match action {
+            // unmapped keys are indistinguishable, so their state isn't tracked
+            _ if key == Key::Unknown => {}
+            Action::Press => {
+                self.inner.keys_down.borrow_mut().insert(key);
+            }
+            Action::Release => {
+                self.inner.keys_down.borrow_mut().remove(&key);
+            }
+            Action::Repeat => {}
+        }
+        self.push_event(Event::Key { key, scancode, action, modifiers });
+        if let Some(callback) = &mut self.on_key {
+            callback(key, scancode, action, modifiers);
+        }
+    }
+
+    fn _on_char(&mut self, c: char) {
+        self.push_event(Event::Char(c));
+        if let Some(callback) = &mut self.on_char {
+            callback(c);
+        }
     }
 }
 
@@ -179,4 +567,16 @@ impl WindowHandle {
     pub fn height(&self) -> i32 {
         self.inner.height.get()
     }
+    #[inline]
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.inner.keys_down.borrow().contains(&key)
+    }
+    #[inline]
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.inner.buttons_down.borrow().contains(&button)
+    }
+    #[inline]
+    pub fn cursor_position(&self) -> (f64, f64) {
+        self.inner.cursor_position.get()
+    }
 }
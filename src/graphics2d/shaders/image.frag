@@ -3,9 +3,20 @@
 in vec2 TexCoord;
 out vec4 FragColor;
 
+#ifdef INSTANCE_TRANSFORM
+in vec4 InstanceColor;                        // tint
+#endif
+
 // texture samples
 uniform sampler2D texture1;
+uniform bool u_premultiply;                   // blending expects premultiplied colors
 
 void main() {
     FragColor = texture(texture1, TexCoord);
-}
\ No newline at end of file
+#ifdef INSTANCE_TRANSFORM
+    FragColor *= InstanceColor;
+#endif
+    if (u_premultiply) {
+        FragColor.rgb *= FragColor.a;
+    }
+}
@@ -1,7 +1,28 @@
 #version 330 core
-uniform vec3 geometryColor;
+#ifdef INSTANCE_TRANSFORM
+in vec4 InstanceColor;
+#else
+uniform vec4 geometryColor;
+#endif
+uniform bool u_premultiply;                   // blending expects premultiplied colors
 out vec4 FragColor;
 void main()
 {
-    FragColor = vec4(geometryColor, 1);
-}
\ No newline at end of file
+#ifdef ROUNDED_POINTS
+    // Coordinates in gl_Point are from (0,0) to (1,1), center at (0.5, 0.5)
+    vec2 coord = gl_PointCoord - vec2(0.5);
+
+    // Discard anything outside radius
+    if (length(coord) > 0.5) {
+        discard;
+    }
+#endif
+#ifdef INSTANCE_TRANSFORM
+    FragColor = InstanceColor;
+#else
+    FragColor = geometryColor;
+#endif
+    if (u_premultiply) {
+        FragColor.rgb *= FragColor.a;
+    }
+}
@@ -1,12 +1,43 @@
 #version 330 core
 
 uniform mat4 u_Transform;                     // projection matrix
-uniform vec2 u_screen_offset;                 // single-shape translation (uniform). Forced to 0 when instancing
+uniform vec2 u_screen_offset;                 // single-shape translation (uniform), unused when instancing
 
 layout (location = 0) in vec2 aPos;           // mesh-local vertex
-layout (location = 1) in vec2 aInstanceXY;    // optional; if disabled => (0,0)
+
+#ifdef INSTANCED
+layout (location = 1) in vec2 aInstanceXY;    // per-instance translation
+#endif
+
+#ifdef TEXTURED
+layout (location = 2) in vec2 aTexCoord;
+out vec2 TexCoord;
+#endif
+
+#ifdef INSTANCE_TRANSFORM
+layout (location = 3) in float aInstanceRotation; // radians, counter-clockwise on screen
+layout (location = 4) in vec2 aInstanceScale;
+layout (location = 5) in vec4 aInstanceColor;
+out vec4 InstanceColor;
+#endif
 
 void main() {
-    vec2 p = aPos + u_screen_offset + aInstanceXY;
+#ifdef INSTANCED
+    vec2 local = aPos;
+#ifdef INSTANCE_TRANSFORM
+    // scale, then rotate around the mesh origin (y points down on screen)
+    local *= aInstanceScale;
+    float c = cos(aInstanceRotation);
+    float s = sin(aInstanceRotation);
+    local = vec2(c * local.x + s * local.y, -s * local.x + c * local.y);
+    InstanceColor = aInstanceColor;
+#endif
+    vec2 p = local + aInstanceXY;
+#else
+    vec2 p = aPos + u_screen_offset;
+#endif
     gl_Position = u_Transform * vec4(p, 0.0, 1.0);
-}
\ No newline at end of file
+#ifdef TEXTURED
+    TexCoord = aTexCoord;
+#endif
+}
@@ -1,5 +1,6 @@
 mod shaperenderable;
 
+pub use shaperenderable::ShapeInstance;
 pub use shaperenderable::ShapeRenderable;
 pub use shaperenderable::ShapeStyle;
 
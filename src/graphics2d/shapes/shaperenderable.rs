@@ -1,9 +1,9 @@
 use crate::core::engine::opengl::{
-    GL_POINTS, GL_TRIANGLE_FAN, GL_TRIANGLE_STRIP, GL_TRIANGLES, GLfloat, Vec2,
+    GL_POINTS, GL_TRIANGLE_FAN, GL_TRIANGLE_STRIP, GL_TRIANGLES, GLenum, GLfloat, Vec2,
 };
 use crate::core::{
-    Attribute, Color, Geometry, Mesh, Renderable, Renderer, Shader, generate_texture_from_image,
-    load_image,
+    Attribute, BlendMode, Color, Geometry, Image as ImageData, Mesh, RenderTarget, Renderable, Renderer,
+    Shader, ShaderVariants, Texture, TextureOptions, Vertex, load_image,
 };
 use crate::graphics2d::shapes::{
     Arc as ArcShape, Circle, Ellipse, Image, Line, MultiPoint, Polygon, Polyline, Rectangle,
@@ -11,18 +11,27 @@ use crate::graphics2d::shapes::{
 };
 use crate::graphics2d::svg::ToSvg;
 use glam::{Mat4, Vec3};
-use std::cell::OnceCell;
 use std::f32::consts::PI;
 use std::rc::Rc;
 
 const MIN_STROKE_WIDTH: f32 = 1.5;
 const SCALE_FACTOR: f32 = 1.0;
 
+/// Number of perimeter vertices of circles.
+const CIRCLE_SEGMENTS: usize = 100;
+/// Number of perimeter vertices of ellipses.
+const ELLIPSE_SEGMENTS: usize = 64;
+/// Number of segments of each rounded rectangle corner.
+const CORNER_SEGMENTS: usize = 8;
+/// Number of segments of arcs.
+const ARC_SEGMENTS: usize = 64;
+
 #[derive(Clone, Debug)]
 pub struct ShapeStyle {
     pub fill: Option<Color>,
     pub stroke_color: Option<Color>,
     pub stroke_width: Option<f32>,
+    pub blend_mode: BlendMode,
 }
 
 impl Default for ShapeStyle {
@@ -31,59 +40,128 @@ impl Default for ShapeStyle {
             fill: Some(Color::from_rgb(1.0, 1.0, 1.0)),
             stroke_color: Some(Color::from_rgb(1.0, 1.0, 1.0)),
             stroke_width: Some(5.0),
+            blend_mode: BlendMode::Alpha,
         }
     }
 }
 
 thread_local! {
-    static DEFAULT_SHADER: OnceCell<Rc<Shader>> = OnceCell::new();
+    static SHAPE_SHADERS: ShaderVariants = ShaderVariants::new(
+        include_str!("../shaders/shape.vert"),
+        include_str!("../shaders/shape.frag"),
+        None,
+    );
+    static IMAGE_SHADERS: ShaderVariants = ShaderVariants::new(
+        include_str!("../shaders/shape.vert"),
+        include_str!("../shaders/image.frag"),
+        None,
+    );
 }
 
-fn default_shader() -> Rc<Shader> {
-    DEFAULT_SHADER.with(|cell| {
-        cell.get_or_init(|| {
-            let vert_src = include_str!("../shaders/shape.vert");
-            let frag_src = include_str!("../shaders/shape.frag");
-            Rc::new(
-                Shader::compile(vert_src, frag_src, None)
-                    .expect("Failed to compile default shader"),
-            )
-        })
-        .clone()
-    })
+/// Per-instance data of a [`ShapeRenderable`] drawn with
+/// [`ShapeRenderable::create_shape_instances`].
+///
+/// Each instance is scaled, then rotated around the shape's origin (its first point, or the
+/// center of circles and ellipses), then moved to `position`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct ShapeInstance {
+    pub position: Vec2,
+    /// Rotation in radians, counter-clockwise on screen.
+    pub rotation: f32,
+    pub scale: Vec2,
+    /// RGBA color, replacing the shape's fill. Images are tinted with it instead.
+    pub color: [f32; 4],
 }
 
-thread_local! {
-    static POINT_SHADER: OnceCell<Rc<Shader>> = OnceCell::new();
+impl ShapeInstance {
+    /// An instance at `position`, unrotated, unscaled and colored `color`.
+    pub fn new(position: Vec2, color: &Color) -> Self {
+        Self {
+            position,
+            rotation: 0.0,
+            scale: Vec2::new(1.0, 1.0),
+            color: [
+                color.red_value(),
+                color.green_value(),
+                color.blue_value(),
+                color.alpha(),
+            ],
+        }
+    }
 }
 
-fn point_shader() -> Rc<Shader> {
-    POINT_SHADER.with(|cell| {
-        cell.get_or_init(|| {
-            let vert_src = include_str!("../shaders/shape.vert");
-            let frag_src = include_str!("../shaders/point.frag");
-            Rc::new(
-                Shader::compile(vert_src, frag_src, None).expect("Failed to compile point shader"),
-            )
+// at the locations read by the built-in shape shaders
+crate::impl_vertex!(ShapeInstance {
+    position => 1 per_instance,
+    rotation => 3 per_instance,
+    scale => 4 per_instance,
+    color => 5 per_instance,
+});
+
+/// How a [`ShapeRenderable`] is instanced, which selects the shader variant.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Instancing {
+    Off,
+    /// One translation per instance, see [`ShapeRenderable::create_multiple_instances`].
+    Positions,
+    /// A [`ShapeInstance`] per instance.
+    Transforms,
+}
+
+/// The built-in shader families, each with a single and instanced variants.
+#[derive(Clone, Copy)]
+enum ShapeShader {
+    Fill,
+    RoundedPoints,
+    Textured,
+}
+
+impl ShapeShader {
+    fn of(shape: &ShapeKind) -> Self {
+        match shape {
+            ShapeKind::Point | ShapeKind::MultiPoint(_) => ShapeShader::RoundedPoints,
+            ShapeKind::Image(_) => ShapeShader::Textured,
+            _ => ShapeShader::Fill,
+        }
+    }
+
+    fn get(self, instancing: Instancing) -> Rc<Shader> {
+        let mut defines = Vec::new();
+        match instancing {
+            Instancing::Off => {}
+            Instancing::Positions => defines.push("INSTANCED"),
+            Instancing::Transforms => defines.extend(["INSTANCED", "INSTANCE_TRANSFORM"]),
+        }
+        let variants = match self {
+            ShapeShader::Fill => &SHAPE_SHADERS,
+            ShapeShader::RoundedPoints => {
+                defines.push("ROUNDED_POINTS");
+                &SHAPE_SHADERS
+            }
+            ShapeShader::Textured => {
+                defines.push("TEXTURED");
+                &IMAGE_SHADERS
+            }
+        };
+        variants.with(|variants| {
+            variants
+                .get(&defines)
+                .unwrap_or_else(|e| panic!("Failed to compile shape shader {defines:?}: {e}"))
         })
-        .clone()
-    })
+    }
 }
 
-thread_local! {
-    static IMAGE_SHADER: OnceCell<Rc<Shader>> = OnceCell::new();
+fn default_shader() -> Rc<Shader> {
+    ShapeShader::Fill.get(Instancing::Off)
+}
+
+fn point_shader() -> Rc<Shader> {
+    ShapeShader::RoundedPoints.get(Instancing::Off)
 }
+
 fn image_shader() -> Rc<Shader> {
-    IMAGE_SHADER.with(|cell| {
-        cell.get_or_init(|| {
-            let vert_src = include_str!("../shaders/image.vert");
-            let frag_src = include_str!("../shaders/image.frag");
-            Rc::new(
-                Shader::compile(vert_src, frag_src, None).expect("Failed to compile image shader"),
-            )
-        })
-        .clone()
-    })
+    ShapeShader::Textured.get(Instancing::Off)
 }
 
 fn ortho_2d_with_zoom(width: f32, height: f32, zoom: f32) -> Mat4 {
@@ -102,18 +180,26 @@ pub struct ShapeRenderable {
     y: f32,
     mesh: Mesh,
     shape: ShapeKind,
+    /// Stroke width the line-like shapes were tessellated with.
+    stroke_width: f32,
+    /// Which shader variant the mesh uses.
+    instancing: Instancing,
+    /// Whether image texture coordinates are flipped vertically, for bottom-up textures.
+    flip_texture: bool,
+    /// Whether the image texture's colors are already multiplied by their alpha.
+    premultiplied_texture: bool,
 }
 impl Renderable for ShapeRenderable {
     fn render(&mut self, renderer: &Renderer) {
-        let (window_width, window_height) = renderer.window_handle.size();
+        let (window_width, window_height) = renderer.surface_size();
         let transform = ortho_2d_with_zoom(window_width as f32, window_height as f32, renderer.zoom_level)
         // use the line below if you want to keep objects of constant size during zoom    
         //* Mat4::from_scale(Vec3::splat(SCALE_FACTOR/renderer.zoom_level));
             * Mat4::from_scale(Vec3::splat(SCALE_FACTOR));
         self.mesh.set_transform(transform);
 
-        if self.mesh.geometry.instance_count() > 0 {
-            // instanced: u_offset = (0,0), positions come from attrib 1
+        if self.instancing != Instancing::Off {
+            // instanced: positions come from attrib 1
             renderer.draw_mesh_instanced(&self.mesh);
         } else {
             // single: use u_offset
@@ -125,7 +211,18 @@ impl Renderable for ShapeRenderable {
 
 impl ShapeRenderable {
     fn new(x: f32, y: f32, mesh: Mesh, shape: ShapeKind) -> Self {
-        Self { x, y, mesh, shape }
+        let mut renderable = Self {
+            x,
+            y,
+            mesh,
+            shape,
+            stroke_width: 1.0,
+            instancing: Instancing::Off,
+            flip_texture: false,
+            premultiplied_texture: false,
+        };
+        renderable.set_blend_mode(BlendMode::Alpha);
+        renderable
     }
 
     pub fn set_position(&mut self, x: f32, y: f32) {
@@ -133,7 +230,7 @@ impl ShapeRenderable {
         self.y = y;
     }
     pub fn from_shape(x: f32, y: f32, shape: ShapeKind, style: ShapeStyle) -> Self {
-        match shape {
+        let mut renderable = match shape {
             ShapeKind::Point => {
                 ShapeRenderable::point(x, y, style.fill.unwrap_or(Color::white()))
             }
@@ -188,23 +285,166 @@ impl ShapeRenderable {
             ShapeKind::Image(_) => {
                 unimplemented!("ShapeRenderable::from_shape cannot create Image without path")
             }
-        }
+        };
+        renderable.set_blend_mode(style.blend_mode);
+        renderable
+    }
+
+    /// Sets how the shape is blended with what is already drawn.
+    ///
+    /// For the modes expecting premultiplied colors, the shape's colors are multiplied by
+    /// their alpha when drawn, except those of render target textures which already are:
+    /// draw those with [`BlendMode::Premultiplied`], the mode [`Self::from_render_target`]
+    /// starts with.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.mesh.set_blend_mode(blend_mode);
+        let premultiply = blend_mode.expects_premultiplied() && !self.premultiplied_texture;
+        self.mesh.set_uniform("u_premultiply", premultiply as i32);
     }
 
     pub fn create_multiple_instances(&mut self, capacity: usize) {
         self.mesh.geometry.enable_instancing_xy(capacity);
+        self.set_instancing(Instancing::Positions);
     }
 
     pub fn set_instance_positions(&mut self, positions: &[Vec2]) {
         self.mesh.geometry.update_instance_xy(positions);
     }
 
+    /// Like [Self::create_multiple_instances], with a position, rotation, scale and color
+    /// per instance, set with [Self::set_instances].
+    pub fn create_shape_instances(&mut self, capacity: usize) {
+        self.mesh
+            .geometry
+            .enable_instancing(&ShapeInstance::attributes(), capacity);
+        self.set_instancing(Instancing::Transforms);
+    }
+
+    pub fn set_instances(&mut self, instances: &[ShapeInstance]) {
+        self.mesh.geometry.update_instances(instances);
+    }
+
     pub fn clear_instances(&mut self) {
         self.mesh.geometry.clear_instancing();
+        self.set_instancing(Instancing::Off);
+    }
+
+    fn set_instancing(&mut self, instancing: Instancing) {
+        self.mesh.shader = ShapeShader::of(&self.shape).get(instancing);
+        self.instancing = instancing;
+    }
+
+    /// Replaces the points of a polyline, polygon, multi-point or triangle (which takes
+    /// exactly three), re-tessellating into the existing vertex buffer.
+    ///
+    /// The points are relative to the current position, see [Self::set_position].
+    pub fn set_points(&mut self, points: &[(f32, f32)]) {
+        let vertices = match &mut self.shape {
+            ShapeKind::Polyline(polyline) => {
+                assert!(points.len() >= 2, "Polyline requires at least two points");
+                polyline.points = points.to_vec();
+                ShapeRenderable::polyline_vertices(points, self.stroke_width)
+            }
+            ShapeKind::Polygon(polygon) => {
+                assert!(points.len() >= 3, "Polygon requires at least 3 points");
+                polygon.points = points.to_vec();
+                ShapeRenderable::point_list_vertices(points)
+            }
+            ShapeKind::MultiPoint(multi_point) => {
+                multi_point.points = points.to_vec();
+                ShapeRenderable::point_list_vertices(points)
+            }
+            ShapeKind::Triangle(triangle) => {
+                triangle.vertices = points
+                    .try_into()
+                    .expect("Triangle requires exactly 3 points");
+                ShapeRenderable::triangle_vertices(&triangle.vertices)
+            }
+            _ => panic!("set_points is not supported for this shape"),
+        };
+        self.mesh.geometry.update_buffer(&vertices, 2);
+    }
+
+    /// Changes the radius of a circle, rounded rectangle or arc, re-tessellating into the
+    /// existing vertex buffer. Arcs keep their center.
+    pub fn set_radius(&mut self, radius: f32) {
+        let vertices = match &mut self.shape {
+            ShapeKind::Circle(circle) => {
+                circle.radius = radius;
+                ShapeRenderable::ellipse_vertices(radius, radius, CIRCLE_SEGMENTS)
+            }
+            ShapeKind::RoundedRectangle(rr) => {
+                rr.radius = radius;
+                ShapeRenderable::rounded_rectangle_vertices(
+                    rr.width,
+                    rr.height,
+                    radius,
+                    CORNER_SEGMENTS,
+                )
+            }
+            ShapeKind::Arc(arc) => {
+                // the position is the first point of the arc, recover the center from it
+                let cx = self.x - arc.radius * arc.start_angle.cos();
+                let cy = self.y + arc.radius * arc.start_angle.sin();
+                arc.radius = radius;
+
+                let points = ShapeRenderable::arc_points(cx, cy, arc);
+                let (x0, y0) = points[0];
+                let rel_points: Vec<(f32, f32)> =
+                    points.iter().map(|(x, y)| (x - x0, y - y0)).collect();
+                self.x = x0;
+                self.y = y0;
+                ShapeRenderable::polyline_vertices(&rel_points, self.stroke_width)
+            }
+            _ => panic!("set_radius is not supported for this shape"),
+        };
+        self.mesh.geometry.update_buffer(&vertices, 2);
+    }
+
+    /// Changes the size of a rectangle, rounded rectangle, ellipse or image,
+    /// re-tessellating into the existing vertex buffer. An ellipse gets radii of half
+    /// `width` and `height`.
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        match &mut self.shape {
+            ShapeKind::Rectangle(rect) => {
+                rect.width = width;
+                rect.height = height;
+                let vertices = ShapeRenderable::rectangle_vertices(width, height);
+                self.mesh.geometry.update_buffer(&vertices, 2);
+            }
+            ShapeKind::RoundedRectangle(rr) => {
+                rr.width = width;
+                rr.height = height;
+                let vertices = ShapeRenderable::rounded_rectangle_vertices(
+                    width,
+                    height,
+                    rr.radius,
+                    CORNER_SEGMENTS,
+                );
+                self.mesh.geometry.update_buffer(&vertices, 2);
+            }
+            ShapeKind::Ellipse(ellipse) => {
+                ellipse.radius_x = width / 2.0;
+                ellipse.radius_y = height / 2.0;
+                let vertices = ShapeRenderable::ellipse_vertices(
+                    ellipse.radius_x,
+                    ellipse.radius_y,
+                    ELLIPSE_SEGMENTS,
+                );
+                self.mesh.geometry.update_buffer(&vertices, 2);
+            }
+            ShapeKind::Image(image) => {
+                image.width = width;
+                image.height = height;
+                let vertices = ShapeRenderable::image_vertices(width, height, self.flip_texture);
+                self.mesh.geometry.update_buffer(&vertices, 4);
+            }
+            _ => panic!("set_size is not supported for this shape"),
+        }
     }
 
     fn point(x: GLfloat, y: GLfloat, color: Color) -> Self {
-        let geometry = ShapeRenderable::point_geometry();
+        let geometry = ShapeRenderable::geometry_2d(GL_POINTS, &[0.0, 0.0]);
         let mesh = Mesh::with_color(point_shader(), geometry, Some(color));
         ShapeRenderable::new(x, y, mesh, ShapeKind::Point)
     }
@@ -222,7 +462,10 @@ impl ShapeRenderable {
         let rel_points: Vec<(GLfloat, GLfloat)> =
             abs_points.iter().map(|(x, y)| (x - x0, y - y0)).collect();
 
-        let geometry = ShapeRenderable::point_list_geometry(&rel_points);
+        let geometry = ShapeRenderable::geometry_2d(
+            GL_POINTS,
+            &ShapeRenderable::point_list_vertices(&rel_points),
+        );
         let mesh = Mesh::with_color(point_shader(), geometry, Some(color));
 
         ShapeRenderable::new(x0, y0, mesh, ShapeKind::MultiPoint(multi_point))
@@ -245,11 +488,14 @@ impl ShapeRenderable {
         let rel_x2 = shape.x2 - x1;
         let rel_y2 = shape.y2 - y1;
 
-        let geometry = ShapeRenderable::line_geometry(0.0, 0.0, rel_x2, rel_y2, stroke_width);
+        let vertices = ShapeRenderable::line_vertices(0.0, 0.0, rel_x2, rel_y2, stroke_width);
+        let geometry = ShapeRenderable::geometry_2d(GL_TRIANGLES, &vertices);
         let mesh = Mesh::with_color(default_shader(), geometry, Some(stroke));
 
         // Drawable positioned at the original start point (x1, y1)
-        ShapeRenderable::new(x1, y1, mesh, ShapeKind::Line(shape))
+        let mut renderable = ShapeRenderable::new(x1, y1, mesh, ShapeKind::Line(shape));
+        renderable.stroke_width = stroke_width;
+        renderable
     }
 
     fn polyline(
@@ -268,10 +514,13 @@ impl ShapeRenderable {
         let (x0, y0) = abs_points[0];
         let rel_points: Vec<(f32, f32)> = abs_points.iter().map(|(x, y)| (x - x0, y - y0)).collect();
 
-        let geometry = ShapeRenderable::polyline_geometry(&rel_points, stroke_width);
+        let vertices = ShapeRenderable::polyline_vertices(&rel_points, stroke_width);
+        let geometry = ShapeRenderable::geometry_2d(GL_TRIANGLES, &vertices);
         let mesh = Mesh::with_color(default_shader(), geometry, Some(stroke));
 
-        ShapeRenderable::new(x0, y0, mesh, ShapeKind::Polyline(polyline))
+        let mut renderable = ShapeRenderable::new(x0, y0, mesh, ShapeKind::Polyline(polyline));
+        renderable.stroke_width = stroke_width;
+        renderable
     }
 
     /// Helper for arc: creates a polyline from pre-computed absolute points
@@ -286,52 +535,46 @@ impl ShapeRenderable {
         let (x0, y0) = points[0];
         let rel_points: Vec<(f32, f32)> = points.iter().map(|(x, y)| (x - x0, y - y0)).collect();
 
-        let geometry = ShapeRenderable::polyline_geometry(&rel_points, stroke_width);
+        let vertices = ShapeRenderable::polyline_vertices(&rel_points, stroke_width);
+        let geometry = ShapeRenderable::geometry_2d(GL_TRIANGLES, &vertices);
         let mesh = Mesh::with_color(default_shader(), geometry, Some(stroke));
 
-        ShapeRenderable::new(x0, y0, mesh, shape)
+        let mut renderable = ShapeRenderable::new(x0, y0, mesh, shape);
+        renderable.stroke_width = stroke_width;
+        renderable
     }
 
     fn arc(x: f32, y: f32, arc: ArcShape, stroke: Color, stroke_width: f32) -> Self {
-        use std::f32::consts::TAU;
-
-        let segments = 64;
-
-        // Normalize sweep to [0, TAU)
-        let mut sweep = arc.end_angle - arc.start_angle;
-        if sweep < 0.0 {
-            sweep += TAU;
-        }
-
-        // Generate points counter-clockwise from start to end
-        let mut points = Vec::with_capacity(segments + 1);
-        for i in 0..=segments {
-            let t = i as f32 / segments as f32;
-            let theta = arc.start_angle + t * sweep;
-            let px = x + arc.radius * theta.cos();
-            let py = y - arc.radius * theta.sin();
-            points.push((px, py));
-        }
-
+        let points = ShapeRenderable::arc_points(x, y, &arc);
         Self::polyline_from_points(&points, ShapeKind::Arc(arc), stroke, stroke_width)
     }
 
     fn triangle(x: f32, y: f32, triangle: Triangle, color: Color) -> Self {
-        let geometry = ShapeRenderable::triangle_geometry(&triangle.vertices);
+        let geometry = ShapeRenderable::geometry_2d(
+            GL_TRIANGLES,
+            &ShapeRenderable::triangle_vertices(&triangle.vertices),
+        );
         let mesh = Mesh::with_color(default_shader(), geometry, Some(color));
 
         ShapeRenderable::new(x, y, mesh, ShapeKind::Triangle(triangle))
     }
 
     fn rectangle(x: f32, y: f32, rect: Rectangle, color: Color) -> Self {
-        let geometry = ShapeRenderable::rectangle_geometry(rect.width, rect.height);
+        let geometry = ShapeRenderable::geometry_2d(
+            GL_TRIANGLE_STRIP,
+            &ShapeRenderable::rectangle_vertices(rect.width, rect.height),
+        );
         let mesh = Mesh::with_color(default_shader(), geometry, Some(color));
         ShapeRenderable::new(x, y, mesh, ShapeKind::Rectangle(rect))
     }
 
     fn rounded_rectangle(x: f32, y: f32, rr: RoundedRectangle, color: Color) -> Self {
-        let geometry =
-            ShapeRenderable::rounded_rectangle_geometry(rr.width, rr.height, rr.radius, 8);
+        let geometry = ShapeRenderable::rounded_rectangle_geometry(
+            rr.width,
+            rr.height,
+            rr.radius,
+            CORNER_SEGMENTS,
+        );
         let mesh = Mesh::with_color(default_shader(), geometry, Some(color));
         ShapeRenderable::new(x, y, mesh, ShapeKind::RoundedRectangle(rr))
     }
@@ -345,20 +588,31 @@ impl ShapeRenderable {
         let (x0, y0) = abs_points[0]; // Anchor
         let rel_points: Vec<(f32, f32)> = abs_points.iter().map(|(x, y)| (x - x0, y - y0)).collect();
 
-        let geometry = ShapeRenderable::polygon_geometry(&rel_points);
+        let geometry = ShapeRenderable::geometry_2d(
+            GL_TRIANGLE_FAN,
+            &ShapeRenderable::point_list_vertices(&rel_points),
+        );
         let mesh = Mesh::with_color(default_shader(), geometry, Some(color));
 
         ShapeRenderable::new(x0, y0, mesh, ShapeKind::Polygon(polygon))
     }
 
     fn circle(x: f32, y: f32, circle: Circle, color: Color) -> Self {
-        let geometry = ShapeRenderable::circle_geometry(circle.radius, 100);
+        let geometry = ShapeRenderable::closed_fan_geometry(&ShapeRenderable::ellipse_vertices(
+            circle.radius,
+            circle.radius,
+            CIRCLE_SEGMENTS,
+        ));
         let mesh = Mesh::with_color(default_shader(), geometry, Some(color));
         ShapeRenderable::new(x, y, mesh, ShapeKind::Circle(circle))
     }
 
     fn ellipse(x: f32, y: f32, ellipse: Ellipse, color: Color) -> Self {
-        let geometry = ShapeRenderable::ellipse_geometry(ellipse.radius_x, ellipse.radius_y, 64);
+        let geometry = ShapeRenderable::closed_fan_geometry(&ShapeRenderable::ellipse_vertices(
+            ellipse.radius_x,
+            ellipse.radius_y,
+            ELLIPSE_SEGMENTS,
+        ));
         let mesh = Mesh::with_color(default_shader(), geometry, Some(color));
         ShapeRenderable::new(x, y, mesh, ShapeKind::Ellipse(ellipse))
     }
@@ -366,75 +620,80 @@ impl ShapeRenderable {
     pub fn image_with_size(x: f32, y: f32, path: &str, width: f32, height: f32) -> ShapeRenderable {
         // Load image data and upload to GPU
         let image = load_image(path);
+        Self::image_from_data(x, y, &image, width, height)
+    }
 
-        let texture_id = generate_texture_from_image(&image);
+    pub fn image(x: f32, y: f32, path: &str) -> Self {
+        let image = load_image(path);
+        Self::image_from_data(x, y, &image, image.width as f32, image.height as f32)
+    }
 
-        // You likely want to query dimensions in `generate_texture_from_image`
-        // But if not, load dimensions again:
-        //let (width, height, _) = load_image(path); // image module only used for size
+    fn image_from_data(x: f32, y: f32, image: &ImageData, width: f32, height: f32) -> Self {
+        let texture = Texture::from_image(image, TextureOptions::default());
+        Self::from_texture(x, y, Rc::new(texture), width, height)
+    }
 
+    /// Draws `texture` stretched over a `width` x `height` rectangle, e.g. a raster overlay
+    /// updated with [`Texture::update_region`].
+    pub fn from_texture(x: f32, y: f32, texture: Rc<Texture>, width: f32, height: f32) -> Self {
         // Create image geometry (2-triangle quad)
         let geometry = ShapeRenderable::image_geometry(width, height);
 
         // Use image shader and attach texture
-        let shader = image_shader(); // assumes you have an Rc<Shader> loader
-        let mesh = Mesh::with_texture(shader, geometry, Some(texture_id));
+        let mesh = Mesh::with_texture(image_shader(), geometry, Some(texture));
 
         ShapeRenderable::new(x, y, mesh, ShapeKind::Image(Image::new(width, height)))
     }
 
-    pub fn image(x: f32, y: f32, path: &str) -> Self {
-        let image = load_image(path);
-        Self::image_with_size(x, y, path, image.width as f32, image.height as f32)
+    /// Draws the texture of `target` at its pixel size, upright.
+    pub fn from_render_target(x: f32, y: f32, target: &RenderTarget) -> Self {
+        let (width, height) = (target.width() as f32, target.height() as f32);
+        // framebuffer textures store the bottom row first
+        let geometry = ShapeRenderable::textured_quad_geometry(width, height, true);
+        let mesh = Mesh::with_texture(image_shader(), geometry, Some(target.texture().clone()));
+
+        let mut renderable =
+            ShapeRenderable::new(x, y, mesh, ShapeKind::Image(Image::new(width, height)));
+        renderable.flip_texture = true;
+        renderable.premultiplied_texture = true;
+        renderable.set_blend_mode(BlendMode::Premultiplied);
+        renderable
     }
 
-    fn point_geometry() -> Geometry {
-        let vertex = vec![0.0, 0.0];
-        let mut geometry = Geometry::new(GL_POINTS);
-        geometry.add_buffer(&vertex, 2);
-
+    /// Builds a geometry with a single 2D position attribute from `[x, y]` pairs.
+    fn geometry_2d(drawing_mode: GLenum, vertices: &[GLfloat]) -> Geometry {
+        let mut geometry = Geometry::new(drawing_mode);
+        geometry.add_buffer(vertices, 2);
         geometry.add_vertex_attribute(Attribute::new(0, 2, 2, 0));
-
         geometry
     }
 
-    fn point_list_geometry(points: &[(GLfloat, GLfloat)]) -> Geometry {
-        let mut vertices = Vec::with_capacity(points.len() * 2);
-
-        for &(x, y) in points {
-            vertices.push(x);
-            vertices.push(y);
-        }
-
-        let values_per_vertex = 2;
-
-        let mut geometry = Geometry::new(GL_POINTS);
-        geometry.add_buffer(&vertices, values_per_vertex);
-
-        geometry.add_vertex_attribute(Attribute::new(
-            0, // position
-            values_per_vertex,
-            values_per_vertex as usize,
-            0,
-        ));
-
+    /// Like [Self::geometry_2d] for a triangle fan made of a center vertex followed by the
+    /// perimeter vertices, closed by the indices.
+    fn closed_fan_geometry(vertices: &[GLfloat]) -> Geometry {
+        let mut geometry = ShapeRenderable::geometry_2d(GL_TRIANGLE_FAN, vertices);
+        ShapeRenderable::set_closed_fan_indices(&mut geometry, vertices.len() / 2 - 1);
         geometry
     }
 
-    fn line_geometry(
+    fn point_list_vertices(points: &[(GLfloat, GLfloat)]) -> Vec<GLfloat> {
+        points.iter().flat_map(|&(x, y)| [x, y]).collect()
+    }
+
+    fn line_vertices(
         x1: GLfloat,
         y1: GLfloat,
         x2: GLfloat,
         y2: GLfloat,
         stroke_width: f32,
-    ) -> Geometry {
+    ) -> Vec<GLfloat> {
         let stroke_width = stroke_width.max(MIN_STROKE_WIDTH);
         let dx = x2 - x1;
         let dy = y2 - y1;
         let length = (dx * dx + dy * dy).sqrt();
 
         if length == 0.0 {
-            return Geometry::new(GL_TRIANGLES);
+            return Vec::new();
         }
 
         // Unit perpendicular vector
@@ -452,22 +711,9 @@ impl ShapeRenderable {
         let v2 = [x2 + ox, y2 + oy];
         let v3 = [x1 + ox, y1 + oy];
 
-        let vertices: Vec<GLfloat> = vec![
+        vec![
             v0[0], v0[1], v1[0], v1[1], v2[0], v2[1], v2[0], v2[1], v3[0], v3[1], v0[0], v0[1],
-        ];
-
-        let position_values_per_vertex = 2;
-
-        let mut geometry = Geometry::new(GL_TRIANGLES);
-        geometry.add_buffer(&vertices, position_values_per_vertex);
-        geometry.add_vertex_attribute(Attribute::new(
-            0,
-            position_values_per_vertex,
-            position_values_per_vertex as usize,
-            0,
-        ));
-
-        geometry
+        ]
     }
 
     /// Polyline triangulation adapted from JVPolyline by Julien Vernay (2025)
@@ -478,16 +724,16 @@ impl ShapeRenderable {
     ///
     /// This implementation is based on the original algorithm,
     /// restructured and translated to idiomatic Rust for use in sky_renderer.
-    fn polyline_geometry(points: &[(GLfloat, GLfloat)], stroke_width: f32) -> Geometry {
+    fn polyline_vertices(points: &[(GLfloat, GLfloat)], stroke_width: f32) -> Vec<GLfloat> {
         const MITER_LIMIT: f32 = 4.0; // Equivalent to JV default
 
+        let mut vertices: Vec<GLfloat> = Vec::new();
         if points.len() < 2 {
-            return Geometry::new(GL_TRIANGLES);
+            return vertices;
         }
 
         let half_thickness = stroke_width.max(1.0) / 2.0;
         let miter_limit_squared = (stroke_width * MITER_LIMIT).powi(2) / 4.0;
-        let mut vertices: Vec<GLfloat> = Vec::new();
 
         let mut a = points[0];
         let mut b = points[1];
@@ -500,9 +746,8 @@ impl ShapeRenderable {
             }
         }
         if (b.0 - a.0).hypot(b.1 - a.1) == 0.0 {
-            return Geometry::new(GL_TRIANGLES);
+            return vertices;
         }
-
         for i in idx + 1..=points.len() {
             let c = if i < points.len() { points[i] } else { a }; // fake point if last
 
@@ -567,115 +812,47 @@ impl ShapeRenderable {
             b = c;
         }
 
-        let mut geometry = Geometry::new(GL_TRIANGLES);
-        geometry.add_buffer(&vertices, 2);
-        geometry.add_vertex_attribute(Attribute::new(0, 2, 2, 0));
-        geometry
+        vertices
     }
 
-    fn triangle_geometry(vertices: &[(f32, f32); 3]) -> Geometry {
-        let mut geometry = Geometry::new(GL_TRIANGLES);
-        let flattened: Vec<f32> = vertices.iter().flat_map(|(x, y)| [*x, *y]).collect();
-
-        geometry.add_buffer(&flattened, 2);
-        geometry.add_vertex_attribute(Attribute::new(0, 2, 2, 0));
-
-        geometry
+    fn triangle_vertices(vertices: &[(f32, f32); 3]) -> Vec<GLfloat> {
+        vertices.iter().flat_map(|&(x, y)| [x, y]).collect()
     }
 
-    fn rectangle_geometry(width: GLfloat, height: GLfloat) -> Geometry {
-        let vertices: Vec<GLfloat> = vec![
+    fn rectangle_vertices(width: GLfloat, height: GLfloat) -> Vec<GLfloat> {
+        vec![
             // bottom-left
             0.0, 0.0, // bottom-right
             width, 0.0, // top-left
             0.0, height, // top-right
             width, height,
-        ];
-
-        let position_values_per_vertex = 2;
-        let values_per_vertex = position_values_per_vertex;
-
-        let mut geometry = Geometry::new(GL_TRIANGLE_STRIP);
-        geometry.add_buffer(&vertices, values_per_vertex);
-
-        geometry.add_vertex_attribute(Attribute::new(
-            0,
-            position_values_per_vertex,
-            values_per_vertex as usize,
-            0,
-        ));
-
-        geometry
+        ]
     }
 
-    fn circle_geometry(radius: GLfloat, segments: usize) -> Geometry {
-        let mut vertices: Vec<GLfloat> = Vec::with_capacity((segments + 2) * 5); // center + segments + wrap-around
-
-        // Center of the circle
-        vertices.extend_from_slice(&[0.0, 0.0]);
-
-        // Outer vertices
-        for i in 0..=segments {
-            let theta = (i as f32 / segments as f32) * std::f32::consts::TAU; // TAU = 2π
-            let x = radius * theta.cos();
-            let y = radius * theta.sin();
-            vertices.extend_from_slice(&[x, y]);
-        }
-
-        let position_values_per_vertex = 2;
-        let values_per_vertex = position_values_per_vertex;
-
-        let mut geometry = Geometry::new(GL_TRIANGLE_FAN);
-        geometry.add_buffer(&vertices, values_per_vertex);
-
-        geometry.add_vertex_attribute(Attribute::new(
-            0,
-            position_values_per_vertex,
-            values_per_vertex as usize,
-            0,
-        ));
-        geometry
-    }
-
-    fn ellipse_geometry(rx: f32, ry: f32, segments: usize) -> Geometry {
-        use std::f32::consts::PI;
-
-        let mut vertices: Vec<GLfloat> = Vec::with_capacity((segments + 2) * 2);
+    fn ellipse_vertices(rx: f32, ry: f32, segments: usize) -> Vec<GLfloat> {
+        let mut vertices: Vec<GLfloat> = Vec::with_capacity((segments + 1) * 2); // center + segments
 
         // Center point (at origin)
         vertices.extend_from_slice(&[0.0, 0.0]);
 
-        // Perimeter points
-        for i in 0..=segments {
-            let angle = 2.0 * PI * (i as f32) / (segments as f32);
-            let x = rx * angle.cos();
-            let y = ry * angle.sin();
-            vertices.extend_from_slice(&[x, y]);
+        // Perimeter points, the fan is closed by the indices
+        for i in 0..segments {
+            let theta = (i as f32 / segments as f32) * std::f32::consts::TAU; // TAU = 2π
+            vertices.extend_from_slice(&[rx * theta.cos(), ry * theta.sin()]);
         }
 
-        let values_per_vertex = 2;
-        let mut geometry = Geometry::new(GL_TRIANGLE_FAN);
-        geometry.add_buffer(&vertices, values_per_vertex);
-
-        geometry.add_vertex_attribute(Attribute::new(
-            0, // position
-            2,
-            values_per_vertex as usize,
-            0,
-        ));
-
-        geometry
+        vertices
     }
 
-    pub fn rounded_rectangle_geometry(
+    fn rounded_rectangle_vertices(
         width: f32,
         height: f32,
         radius: f32,
         segments: usize,
-    ) -> Geometry {
+    ) -> Vec<GLfloat> {
         assert!(radius * 2.0 <= width && radius * 2.0 <= height);
 
-        let mut vertices: Vec<GLfloat> = Vec::new();
+        let mut vertices: Vec<GLfloat> = Vec::with_capacity((4 * (segments + 1) + 1) * 2);
 
         // 1. Add center point for triangle fan
         let center_x = width / 2.0;
@@ -691,76 +868,83 @@ impl ShapeRenderable {
             (radius, height - radius, 0.5 * PI, PI),          // bottom-left
         ];
 
-        let mut first_arc_x = 0.0;
-        let mut first_arc_y = 0.0;
-        let mut is_first = true;
-
-        // 3. Generate corner arcs
+        // 3. Generate corner arcs, the indices close the fan on the first outer point
         for &(cx, cy, start_angle, end_angle) in &corners {
             for i in 0..=segments {
                 let theta =
                     start_angle + (end_angle - start_angle) * (i as f32) / (segments as f32);
-                let x = cx + radius * theta.cos();
-                let y = cy + radius * theta.sin();
-
-                if is_first {
-                    first_arc_x = x;
-                    first_arc_y = y;
-                    is_first = false;
-                }
-
-                vertices.push(x);
-                vertices.push(y);
+                vertices.push(cx + radius * theta.cos());
+                vertices.push(cy + radius * theta.sin());
             }
         }
 
-        // 4. Close the fan by repeating the first outer point
-        vertices.push(first_arc_x);
-        vertices.push(first_arc_y);
-
-        // 5. Build Geometry
-        let values_per_vertex = 2;
-        let mut geometry = Geometry::new(GL_TRIANGLE_FAN);
-        geometry.add_buffer(&vertices, values_per_vertex);
+        vertices
+    }
 
-        geometry.add_vertex_attribute(Attribute::new(
-            0, // location 0 → position
-            2, // x and y
-            values_per_vertex as usize,
-            0,
-        ));
+    pub fn rounded_rectangle_geometry(
+        width: f32,
+        height: f32,
+        radius: f32,
+        segments: usize,
+    ) -> Geometry {
+        ShapeRenderable::closed_fan_geometry(&ShapeRenderable::rounded_rectangle_vertices(
+            width, height, radius, segments,
+        ))
+    }
 
-        geometry
+    /// Indexes a triangle fan made of a center vertex followed by `outer` perimeter
+    /// vertices, closing it back on the first perimeter vertex.
+    fn set_closed_fan_indices(geometry: &mut Geometry, outer: usize) {
+        let indices = std::iter::once(0).chain(1..=outer).chain(std::iter::once(1));
+        if outer < u16::MAX as usize {
+            geometry.set_indices(&indices.map(|i| i as u16).collect::<Vec<_>>());
+        } else {
+            geometry.set_indices(&indices.map(|i| i as u32).collect::<Vec<_>>());
+        }
     }
 
-    fn polygon_geometry(points: &[(GLfloat, GLfloat)]) -> Geometry {
-        assert!(points.len() >= 3, "Polygon requires at least 3 points");
+    /// Points along `arc` around the center `(cx, cy)`, counter-clockwise from its start
+    /// angle.
+    fn arc_points(cx: f32, cy: f32, arc: &ArcShape) -> Vec<(f32, f32)> {
+        use std::f32::consts::TAU;
 
-        let mut vertices = Vec::with_capacity(points.len() * 2);
-        for &(x, y) in points {
-            vertices.extend_from_slice(&[x, y]);
+        // Normalize sweep to [0, TAU)
+        let mut sweep = arc.end_angle - arc.start_angle;
+        if sweep < 0.0 {
+            sweep += TAU;
         }
 
-        let values_per_vertex = 2;
-        let mut geometry = Geometry::new(GL_TRIANGLE_FAN); // Or TRIANGLE_FAN if filled
-        geometry.add_buffer(&vertices, values_per_vertex);
-        geometry.add_vertex_attribute(Attribute::new(0, 2, values_per_vertex as usize, 0));
-        geometry
+        (0..=ARC_SEGMENTS)
+            .map(|i| {
+                let t = i as f32 / ARC_SEGMENTS as f32;
+                let theta = arc.start_angle + t * sweep;
+                (cx + arc.radius * theta.cos(), cy - arc.radius * theta.sin())
+            })
+            .collect()
     }
 
-    pub fn image_geometry(width: f32, height: f32) -> Geometry {
+    /// The textured quad, with `v` running from 1 to 0 instead when `flip_v` is set.
+    fn image_vertices(width: f32, height: f32, flip_v: bool) -> Vec<GLfloat> {
+        let (v0, v1) = if flip_v { (1.0, 0.0) } else { (0.0, 1.0) };
         // Vertex format: [x, y, u, v]
-        let vertices: Vec<f32> = vec![
+        vec![
             // Triangle 1
-            0.0, 0.0, 0.0, 0.0, // bottom-left
-            width, 0.0, 1.0, 0.0, // bottom-right
-            width, height, 1.0, 1.0, // top-right
+            0.0, 0.0, 0.0, v0, // bottom-left
+            width, 0.0, 1.0, v0, // bottom-right
+            width, height, 1.0, v1, // top-right
             // Triangle 2
-            0.0, 0.0, 0.0, 0.0, // bottom-left
-            width, height, 1.0, 1.0, // top-right
-            0.0, height, 0.0, 1.0, // top-left
-        ];
+            0.0, 0.0, 0.0, v0, // bottom-left
+            width, height, 1.0, v1, // top-right
+            0.0, height, 0.0, v1, // top-left
+        ]
+    }
+
+    pub fn image_geometry(width: f32, height: f32) -> Geometry {
+        ShapeRenderable::textured_quad_geometry(width, height, false)
+    }
 
+    fn textured_quad_geometry(width: f32, height: f32, flip_v: bool) -> Geometry {
+        let vertices = ShapeRenderable::image_vertices(width, height, flip_v);
         let values_per_vertex = 4; // x, y, u, v
 
         let mut geometry = Geometry::new(GL_TRIANGLES);
@@ -774,7 +958,7 @@ impl ShapeRenderable {
         ));
 
         geometry.add_vertex_attribute(Attribute::new(
-            1, // location 1 in shader: texcoord
+            2, // location 2 in shader: texcoord
             2, // u, v
             values_per_vertex as usize,
             2, // offset by 2 floats (x, y)
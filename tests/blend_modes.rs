@@ -0,0 +1,52 @@
+// Draws translucent shapes in each blend mode and reads back the result, so that the
+// shape shaders are checked along with the blend factors. Requires Mesa's libOSMesa.
+
+use sky_renderer::core::{BlendMode, Color, Renderable, Renderer, Window};
+use sky_renderer::graphics2d::shapes::{Rectangle, ShapeKind, ShapeRenderable, ShapeStyle};
+
+const SIZE: i32 = 32;
+const BACKGROUND: f32 = 0.4;
+
+/// The red channel of the center pixel after drawing `fill` over the background in `mode`.
+fn blended(renderer: &Renderer, fill: Color, blend_mode: BlendMode) -> f32 {
+    renderer.clear(&Color::from_rgb(BACKGROUND, BACKGROUND, BACKGROUND));
+    let mut square = ShapeRenderable::from_shape(
+        0.0,
+        0.0,
+        ShapeKind::Rectangle(Rectangle::new(SIZE as f32, SIZE as f32)),
+        ShapeStyle {
+            fill: Some(fill),
+            stroke_color: None,
+            stroke_width: None,
+            blend_mode,
+        },
+    );
+    square.render(renderer);
+    let pixel = renderer.read_pixels(SIZE / 2, SIZE / 2, 1, 1);
+    pixel.pixels[0] as f32 / 255.0
+}
+
+#[test]
+fn translucent_shapes_blend_by_their_alpha() {
+    let window = Window::headless(SIZE, SIZE);
+    let renderer = Renderer::new(window.handle());
+    let white = Color::from_rgba(1.0, 1.0, 1.0, 0.5);
+    let black = Color::from_rgba(0.0, 0.0, 0.0, 0.5);
+
+    let cases = [
+        (BlendMode::Alpha, white.clone(), 0.7),
+        (BlendMode::Premultiplied, white.clone(), 0.7),
+        (BlendMode::Additive, white.clone(), 0.9),
+        (BlendMode::Multiply, black.clone(), 0.2),
+        (BlendMode::Multiply, white.clone(), BACKGROUND),
+        (BlendMode::Screen, white.clone(), 0.7),
+        (BlendMode::Screen, black, BACKGROUND),
+    ];
+    for (mode, fill, expected) in cases {
+        let actual = blended(&renderer, fill.clone(), mode);
+        assert!(
+            (actual - expected).abs() <= 2.0 / 255.0,
+            "{mode:?} with {fill:?}: expected {expected}, got {actual}"
+        );
+    }
+}